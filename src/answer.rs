@@ -0,0 +1,78 @@
+use std::fmt;
+
+/// Solution to a single part of a puzzle.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Multi-line answer, e.g. letters drawn on a screen
+    Image(String),
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(num: $t) -> Self {
+                    Answer::Number(num.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Answer {
+    fn from(num: usize) -> Self {
+        Answer::Number(num as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Image(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Image(image) => write!(f, "{}", image.trim_end()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_numbers() {
+        assert_eq!(Answer::from(42u32), Answer::Number(42));
+        assert_eq!(Answer::from(-3i32), Answer::Number(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::Number(u64::MAX as i128));
+        assert_eq!(Answer::from(7usize), Answer::Number(7));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::Number(-12).to_string(), "-12");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::Image("#.\n.#\n".to_owned()).to_string(), "#.\n.#");
+    }
+}
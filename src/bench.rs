@@ -0,0 +1,150 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::solution::{catch, Solution};
+
+/// Durations of repeated runs of a single stage.
+#[derive(Debug, Default, Clone)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    fn push(&mut self, sample: Duration) {
+        let idx = self.samples.partition_point(|s| *s <= sample);
+        self.samples.insert(idx, sample);
+    }
+
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let len = self.samples.len();
+        match len {
+            0 => Duration::ZERO,
+            _ if len.is_multiple_of(2) => (self.samples[len / 2 - 1] + self.samples[len / 2]) / 2,
+            _ => self.samples[len / 2],
+        }
+    }
+}
+
+impl FromIterator<Duration> for Timings {
+    fn from_iter<I: IntoIterator<Item = Duration>>(iter: I) -> Self {
+        let mut timings = Timings::default();
+        for sample in iter {
+            timings.push(sample);
+        }
+        timings
+    }
+}
+
+/// Timings of parsing and of both parts, each part fails on its own.
+#[derive(Debug)]
+pub struct Bench {
+    pub parse: Timings,
+    pub part_one: Result<Timings>,
+    pub part_two: Result<Timings>,
+}
+
+/// Parses the input and solves both parts `runs` times, the parts are
+/// timed on input parsed in the same run.
+pub fn bench<S: Solution>(input: &str, runs: usize) -> Result<Bench> {
+    let mut parse = Timings::default();
+    let mut part_one = Ok(Timings::default());
+    let mut part_two = Ok(Timings::default());
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = catch(|| S::parse(black_box(input)))?;
+        parse.push(start.elapsed());
+
+        time_part(&mut part_one, || S::part_one(&parsed));
+        time_part(&mut part_two, || S::part_two(&parsed));
+    }
+
+    Ok(Bench {
+        parse,
+        part_one,
+        part_two,
+    })
+}
+
+/// Adds a sample unless the part already failed in a previous run.
+fn time_part<T>(timings: &mut Result<Timings>, part: impl FnOnce() -> Result<T>) {
+    if let Ok(samples) = timings {
+        let start = Instant::now();
+        match catch(part) {
+            Ok(answer) => {
+                samples.push(start.elapsed());
+                black_box(answer);
+            }
+            Err(err) => *timings = Err(err),
+        }
+    }
+}
+
+/// Formats a duration with a unit fitting its magnitude.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_six::DaySix;
+    use crate::error::Error;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn timings_stats() {
+        let timings: Timings = [ms(5), ms(1), ms(3)].into_iter().collect();
+        assert_eq!(timings.runs(), 3);
+        assert_eq!(timings.min(), ms(1));
+        assert_eq!(timings.median(), ms(3));
+        assert_eq!(timings.max(), ms(5));
+
+        let timings: Timings = [ms(4), ms(1), ms(2), ms(8)].into_iter().collect();
+        assert_eq!(timings.median(), ms(3));
+
+        let timings = Timings::default();
+        assert_eq!(timings.median(), Duration::ZERO);
+    }
+
+    #[test]
+    fn bench_runs() {
+        let bench = bench::<DaySix>("abcd", 3).unwrap();
+        assert_eq!(bench.parse.runs(), 3);
+        assert_eq!(bench.part_one.unwrap().runs(), 3);
+        assert!(matches!(bench.part_two, Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_340)), "12.3µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(ms(2_250)), "2.25s");
+    }
+}
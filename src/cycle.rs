@@ -0,0 +1,190 @@
+//! Cycle detection for deterministic simulations, to get the state
+//! after far more steps than could be simulated.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States of a simulation up to the first repetition. Step `offset` is
+/// the first state of the cycle, which repeats every `period` steps.
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    pub offset: usize,
+    pub period: usize,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Cycle<S> {
+    /// Steps from `start` until a state repeats. Never returns for a
+    /// simulation without a cycle.
+    pub fn find(start: S, step: impl FnMut(&S) -> S) -> Self {
+        Self::find_by_key(start, step, S::clone)
+    }
+}
+
+impl<S> Cycle<S> {
+    /// Like [`Cycle::find`], with states compared by `key` only, e.g. to
+    /// leave out a counter that doesn't influence the following steps.
+    pub fn find_by_key<K: Eq + Hash>(
+        start: S,
+        mut step: impl FnMut(&S) -> S,
+        mut key: impl FnMut(&S) -> K,
+    ) -> Self {
+        let mut seen = HashMap::new();
+        let mut states = vec![start];
+
+        loop {
+            let state = states.last().unwrap();
+            if let Some(offset) = seen.insert(key(state), states.len() - 1) {
+                let period = states.len() - 1 - offset;
+                states.pop();
+                return Cycle {
+                    offset,
+                    period,
+                    states,
+                };
+            }
+            let next = step(state);
+            states.push(next);
+        }
+    }
+
+    /// State after `steps` steps.
+    pub fn state_at(&self, steps: usize) -> &S {
+        &self.states[self.index(steps)]
+    }
+
+    /// Index in the recorded states that step `steps` maps to.
+    pub fn index(&self, steps: usize) -> usize {
+        if steps < self.offset {
+            steps
+        } else {
+            self.offset + (steps - self.offset) % self.period
+        }
+    }
+
+    /// States before the cycle followed by one period of it.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// State after `steps` steps, simulated only until a state repeats.
+pub fn fast_forward<S, K: Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+    steps: usize,
+) -> S {
+    // counting the steps, all states from `steps` on share a key so the
+    // search ends there without a cycle, after one more step
+    let cycle = Cycle::find_by_key(
+        (start, 0),
+        |(state, n)| (step(state), n + 1),
+        |(state, n)| (*n < steps).then(|| key(state)),
+    );
+    let index = cycle.index(steps);
+    cycle.states.into_iter().nth(index).unwrap().0
+}
+
+/// Brent's algorithm, returns `(offset, period)` keeping only a couple
+/// of states around instead of all of them. Needs more steps than
+/// [`Cycle::find`] and never returns for a simulation without a cycle.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    // find the period with the hare racing ahead of a tortoise that
+    // teleports to it at every power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // with the hare one period ahead both meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    (offset, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, 3, 4, ...
+    fn rho(state: &u32) -> u32 {
+        if *state < 6 {
+            state + 1
+        } else {
+            3
+        }
+    }
+
+    #[test]
+    fn find() {
+        let cycle = Cycle::find(0, rho);
+        assert_eq!((cycle.offset, cycle.period), (3, 4));
+        assert_eq!(cycle.states(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(cycle.state_at(2), &2);
+        assert_eq!(cycle.state_at(7), &3);
+        assert_eq!(cycle.state_at(1_000_000_000_000), &4);
+        assert_eq!(cycle.state_at(1_000_000_000_002), &6);
+
+        let cycle = Cycle::find(5, |x| (x * x + 1) % 11);
+        assert_eq!(cycle.states(), &[5, 4, 6]);
+        assert_eq!((cycle.offset, cycle.period), (1, 2));
+        assert_eq!(cycle.state_at(100), &6);
+
+        let cycle = Cycle::find(1, |x| x * x);
+        assert_eq!((cycle.offset, cycle.period), (0, 1));
+    }
+
+    #[test]
+    fn find_by_key() {
+        // a counter that keeps counting doesn't prevent the cycle, the
+        // state returned is the first one with the same key
+        let cycle = Cycle::find_by_key((0, 0), |&(x, n)| (rho(&x), n + 1), |&(x, _)| x);
+        assert_eq!((cycle.offset, cycle.period), (3, 4));
+        assert_eq!(cycle.state_at(12), &(4, 4));
+    }
+
+    #[test]
+    fn fast_forward_steps() {
+        for steps in 0..20 {
+            let mut state = 0;
+            for _ in 0..steps {
+                state = rho(&state);
+            }
+            assert_eq!(fast_forward(0, rho, |x| *x, steps), state);
+        }
+        assert_eq!(fast_forward(0, rho, |x| *x, 1_000_000_000_000), 4);
+
+        // simulated directly as long as no state repeats
+        assert_eq!(fast_forward(0u64, |x| x + 1, |x| *x, 1000), 1000);
+    }
+
+    #[test]
+    fn brent_matches_find() {
+        assert_eq!(brent(0, rho), (3, 4));
+        for start in 0..50u64 {
+            let step = |x: &u64| (x * x + 1) % 1009;
+            let cycle = Cycle::find(start, step);
+            assert_eq!(brent(start, step), (cycle.offset, cycle.period));
+        }
+    }
+}
@@ -1,39 +1,52 @@
-use ndarray::{s, Array, Array1, Array2};
+use ndarray::{s, Array, Array2};
 use std::cmp;
 
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::grid::{cell_error, Grid};
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayEight>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let treemap = parse_map(input);
-    let vismap = gen_vismap(&treemap);
-    vismap.mapv(|num| if num == 0 { 0 } else { 1 }).sum()
+pub fn part_one(input: &str) -> Result<u32> {
+    DayEight::part_one(&DayEight::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let treemap = parse_map(input);
-    max_scenic_score(&treemap)
+pub fn part_two(input: &str) -> Result<u32> {
+    DayEight::part_two(&DayEight::parse(input)?)
 }
 
-fn parse_map(input: &str) -> Array2<u32> {
-    let mut line_iter_peek = input.lines().peekable();
-    let cols = line_iter_peek.peek().expect("No input").len();
-    let treemap: Array1<u32> = line_iter_peek
-        .flat_map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).expect("Couldn't parse num"))
-        })
-        .collect();
+pub struct DayEight;
+
+impl Solution for DayEight {
+    const DAY: u32 = 8;
+
+    type Input = Array2<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Array2<u32>> {
+        parse_map(input)
+    }
+
+    fn part_one(treemap: &Array2<u32>) -> Result<u32> {
+        let vismap = gen_vismap(treemap);
+        Ok(vismap.mapv(|num| if num == 0 { 0 } else { 1 }).sum())
+    }
 
-    let rows = treemap.len() / cols;
+    fn part_two(treemap: &Array2<u32>) -> Result<u32> {
+        Ok(max_scenic_score(treemap))
+    }
+}
 
-    treemap.into_shape((rows, cols)).expect("Invalid shape")
-    // .reversed_axes()
+fn parse_map(input: &str) -> Result<Array2<u32>> {
+    let treemap = Grid::parse(input, |pos, c| {
+        c.to_digit(10)
+            .ok_or_else(|| cell_error(pos, "Couldn't parse num"))
+    })?;
+    Ok(treemap.into_array())
 }
 
 fn gen_vismap(treemap: &Array2<u32>) -> Array2<u32> {
@@ -143,6 +156,7 @@ fn max_scenic_score(treemap: &Array2<u32>) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
     use indoc::indoc;
     use ndarray::arr2;
 
@@ -155,7 +169,7 @@ mod tests {
             33549
             35390
         "};
-        let map = parse_map(map);
+        let map = parse_map(map).unwrap();
 
         assert_eq!(&map[[1, 2]], &5);
         assert_eq!(&map[[1, 3]], &1);
@@ -184,7 +198,10 @@ mod tests {
             2551
         "};
 
-        assert_eq!(parse_map(map), arr2(&[[3, 0, 3, 7], [2, 5, 5, 1],]));
+        assert_eq!(
+            parse_map(map).unwrap(),
+            arr2(&[[3, 0, 3, 7], [2, 5, 5, 1],])
+        );
     }
 
     #[test]
@@ -239,4 +256,13 @@ mod tests {
         let most_scenic = max_scenic_score(&map);
         assert_eq!(most_scenic, 1);
     }
+
+    #[test]
+    fn read_input_invalid() {
+        assert_eq!(parse_map(""), Err(Error::parse(1, 1, "No input")));
+        assert_eq!(
+            parse_map("303\n2x5\n"),
+            Err(Error::parse(2, 2, "Couldn't parse num"))
+        );
+    }
 }
@@ -1,89 +1,114 @@
 use indexmap::IndexMap;
 use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
 use std::{
     collections::{HashMap, VecDeque},
-    io::{Error, ErrorKind},
+    num::NonZeroU64,
     str::FromStr,
 };
 
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::math;
+use crate::parse::Fields;
+use crate::regex;
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayEleven>(input)
 }
 
-pub fn part_one(input: &str) -> u64 {
-    let monkeys: Vec<Monkey> = input
-        .split("\n\n")
-        .map(|split| split.parse())
-        .collect::<Result<_, _>>()
-        .unwrap();
+pub fn part_one(input: &str) -> Result<u64> {
+    DayEleven::part_one(&DayEleven::parse(input)?)
+}
 
-    let mut ka = KeepAway::new(monkeys, 3);
+pub fn part_two(input: &str) -> Result<u64> {
+    DayEleven::part_two(&DayEleven::parse(input)?)
+}
 
-    for _ in 0..20 {
-        ka.round();
-    }
+pub struct DayEleven;
 
-    ka.inspections
-        .values()
-        .sorted()
-        .rev()
-        .take(2)
-        .copied()
-        .fold(1, |acc, x| acc * x as u64)
-}
+impl Solution for DayEleven {
+    const DAY: u32 = 11;
 
-pub fn part_two(input: &str) -> u64 {
-    let monkeys: Vec<Monkey> = input
-        .split("\n\n")
-        .map(|split| split.parse())
-        .collect::<Result<_, _>>()
-        .unwrap();
+    type Input = Vec<Monkey>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    let mut ka = KeepAway::new(monkeys, 1);
+    fn parse(input: &str) -> Result<Vec<Monkey>> {
+        let mut monkeys: Vec<Monkey> = Vec::new();
+        let mut monkey_lines = Vec::new();
+        let mut preceding_lines = 0;
 
-    for _ in 0..10000 {
-        ka.round();
+        for split in input.split("\n\n") {
+            let monkey = split
+                .parse()
+                .map_err(|err: Error| err.shifted(preceding_lines))?;
+            monkeys.push(monkey);
+            monkey_lines.push(preceding_lines);
+            // the blank line separating monkeys is not part of the split
+            preceding_lines += split.lines().count() + 1;
+        }
+
+        for (monkey, preceding_lines) in monkeys.iter().zip(monkey_lines) {
+            for (line, target) in [(5, monkey.true_target), (6, monkey.false_target)] {
+                if !monkeys.iter().any(|m| m.id == target) {
+                    return Err(Error::parse(
+                        preceding_lines + line,
+                        1,
+                        format!("Unknown target monkey {}", target),
+                    ));
+                }
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part_one(monkeys: &Vec<Monkey>) -> Result<u64> {
+        let mut ka = KeepAway::new(monkeys.iter().cloned(), 3)?;
+
+        for _ in 0..20 {
+            ka.round();
+        }
+
+        Ok(ka.monkey_business())
     }
 
-    ka.inspections
-        .values()
-        .sorted()
-        .rev()
-        .take(2)
-        .copied()
-        .fold(1, |acc, x| acc * x as u64)
+    fn part_two(monkeys: &Vec<Monkey>) -> Result<u64> {
+        let mut ka = KeepAway::new(monkeys.iter().cloned(), 1)?;
+
+        for _ in 0..10000 {
+            ka.round();
+        }
+
+        Ok(ka.monkey_business())
+    }
 }
 
 type WorryLevel = u64;
 type MonkeyId = u32;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 enum OpArg {
     Old,
     Value(WorryLevel),
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 enum Op {
     Multiply,
     Add,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 struct InfixOp {
     left: OpArg,
     op: Op,
     right: OpArg,
 }
 
-#[derive(PartialEq, Debug)]
-struct Monkey {
+#[derive(PartialEq, Debug, Clone)]
+pub struct Monkey {
     id: MonkeyId,
     items: VecDeque<WorryLevel>,
     op: InfixOp,
@@ -102,91 +127,58 @@ impl FromStr for Monkey {
     ///   Test: divisible by 23
     ///     If true: throw to monkey 2
     ///     If false: throw to monkey 3
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(concat!(
-                r"Monkey (?P<id>\d+):\n",
-                r"  Starting items: (?P<items>(?:\d+, )*\d+)\n",
-                r"  Operation: new = (?P<oparg1>old|\d+) (?P<op>[[:punct:]]) (?P<oparg2>old|\d+)\n",
-                r"  Test: divisible by (?P<divtest>\d+)\n",
-                r"    If true: throw to monkey (?P<truetgt>\d+)\n",
-                r"    If false: throw to monkey (?P<falsetgt>\d+)",
-            ))
-            .unwrap();
-        }
-
-        let caps = RE.captures(s).ok_or(Self::Err::new(
-            ErrorKind::InvalidData,
-            "Couldn't parse monkey",
-        ))?;
-
-        // We now id is there bc. the regex matches
-        let id = caps["id"]
-            .parse()
-            .map_err(|_| Self::Err::new(ErrorKind::InvalidData, "Invalid monkey id"))?;
-
-        // We now items is there bc. the regex matches
-        let items: VecDeque<WorryLevel> = caps["items"]
-            .split(", ")
-            .map(|item| item.parse())
-            .collect::<Result<_, _>>()
-            .map_err(|_| Self::Err::new(ErrorKind::InvalidData, "Couldn't parse items"))?;
-
-        // We now oparg1 is there bc. the regex matches
-        let oparg1 =
-            match &caps["oparg1"] {
-                "old" => OpArg::Old,
-                val => OpArg::Value(val.parse().map_err(|_| {
-                    Self::Err::new(ErrorKind::InvalidData, "Couldn't parse op arg 1")
-                })?),
-            };
-
-        // We now oparg2 is there bc. the regex matches
-        let oparg2 =
-            match &caps["oparg2"] {
-                "old" => OpArg::Old,
-                val => OpArg::Value(val.parse().map_err(|_| {
-                    Self::Err::new(ErrorKind::InvalidData, "Couldn't parse op arg 2")
-                })?),
-            };
-
-        // We now op is there bc. the regex matches
-        let op = match &caps["op"] {
-            "*" => Ok(Op::Multiply),
-            "+" => Ok(Op::Add),
-            _ => Err(Self::Err::new(ErrorKind::InvalidData, "Unsupported Op")),
-        }?;
-
-        // We now divtest is there bc. the regex matches
-        let test_div = caps["divtest"]
-            .parse()
-            .map_err(|_| Self::Err::new(ErrorKind::InvalidData, "Invalid divisor"))?;
-
-        // We now truetgt is there bc. the regex matches
-        let true_target = caps["truetgt"]
-            .parse()
-            .map_err(|_| Self::Err::new(ErrorKind::InvalidData, "Invalid true target monkey id"))?;
-
-        // We now falsetgt is there bc. the regex matches
-        let false_target = caps["falsetgt"].parse().map_err(|_| {
-            Self::Err::new(ErrorKind::InvalidData, "Invalid false target monkey id")
-        })?;
+    fn from_str(s: &str) -> Result<Self> {
+        let re = regex!(concat!(
+            r"Monkey (?P<id>\d+):\n",
+            r"  Starting items: (?P<items>(?:\d+, )*\d+)\n",
+            r"  Operation: new = (?P<left>old|\d+) (?P<op>[[:punct:]]) (?P<right>old|\d+)\n",
+            r"  Test: divisible by (?P<divisor>\d+)\n",
+            r"    If true: throw to monkey (?P<true_target>\d+)\n",
+            r"    If false: throw to monkey (?P<false_target>\d+)",
+        ));
+        let fields = Fields::capture(re, s, "Couldn't parse monkey")?;
 
         Ok(Monkey {
-            id,
-            items,
+            id: fields.get("id")?,
+            items: fields.list("items", ", ")?,
             op: InfixOp {
-                left: oparg1,
-                op,
-                right: oparg2,
+                left: fields.get("left")?,
+                op: fields.get("op")?,
+                right: fields.get("right")?,
             },
-            test_div,
-            true_target,
-            false_target,
+            test_div: fields.get::<NonZeroU64>("divisor")?.get(),
+            true_target: fields.get("true_target")?,
+            false_target: fields.get("false_target")?,
         })
     }
 }
 
+impl FromStr for OpArg {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "old" => Ok(OpArg::Old),
+            val => val
+                .parse()
+                .map(OpArg::Value)
+                .map_err(|_| Error::parse(1, 1, "Couldn't parse op arg")),
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "*" => Ok(Op::Multiply),
+            "+" => Ok(Op::Add),
+            _ => Err(Error::parse(1, 1, "Unsupported Op")),
+        }
+    }
+}
+
 type MonkeyThrow = (MonkeyId, WorryLevel);
 
 impl Monkey {
@@ -255,25 +247,26 @@ struct KeepAway {
 }
 
 impl KeepAway {
-    fn new(monkeys: impl IntoIterator<Item = Monkey>, worry_div: WorryLevel) -> Self {
+    /// Worry levels are kept modulo the lcm of all divisors, which
+    /// preserves every monkey's test.
+    fn new(monkeys: impl IntoIterator<Item = Monkey>, worry_div: WorryLevel) -> Result<Self> {
         let mut idxmap = IndexMap::new();
 
         for monkey in monkeys {
             idxmap.insert(monkey.id, monkey);
         }
 
-        let lcm = idxmap
-            .values()
-            .map(|m| m.test_div)
-            .fold(1, |acc, x| acc * x);
+        let lcm = math::lcm_all(idxmap.values().map(|m| m.test_div))
+            .ok_or_else(|| Error::no_solution("Divisors exceed the worry level range"))?;
 
-        Self {
+        Ok(Self {
             monkeys: idxmap,
             inspections: HashMap::default(),
             worry_div,
             lcm,
-        }
+        })
     }
+
     /// The monkeys take turns inspecting and throwing items. On a single
     /// monkey's turn, it inspects and throws all of the items it is holding
     /// one at a time and in the order listed. Monkey 0 goes first,
@@ -302,11 +295,23 @@ impl KeepAway {
             }
         }
     }
+
+    /// Product of the inspection counts of the two most active monkeys.
+    fn monkey_business(&self) -> u64 {
+        self.inspections
+            .values()
+            .sorted()
+            .rev()
+            .take(2)
+            .copied()
+            .fold(1, |acc, x| acc * x as u64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cycle::{brent, fast_forward, Cycle};
     use indoc::indoc;
 
     const MONKEYS: [&str; 4] = [
@@ -470,7 +475,8 @@ mod tests {
                 .map(|m| m.parse().unwrap())
                 .collect::<Vec<_>>(),
             3,
-        );
+        )
+        .unwrap();
 
         ka.round();
         assert_eq!(ka.monkeys[0].items, vec![20, 23, 27, 26]);
@@ -493,7 +499,8 @@ mod tests {
                 .map(|m| m.parse().unwrap())
                 .collect::<Vec<_>>(),
             3,
-        );
+        )
+        .unwrap();
 
         for _ in 0..20 {
             ka.round();
@@ -503,4 +510,73 @@ mod tests {
         assert_eq!(ka.inspections[&2], 7);
         assert_eq!(ka.inspections[&3], 105);
     }
+
+    #[test]
+    fn keep_away_lcm() {
+        let monkeys: Vec<Monkey> = MONKEYS.iter().map(|m| m.parse().unwrap()).collect();
+        let ka = KeepAway::new(monkeys.iter().cloned(), 1).unwrap();
+        assert_eq!(ka.lcm, 96577);
+
+        // shared factors don't multiply up
+        let divisors = [4, 6, 10, 15];
+        let ka = KeepAway::new(
+            monkeys
+                .into_iter()
+                .zip(divisors)
+                .map(|(monkey, test_div)| Monkey { test_div, ..monkey }),
+            1,
+        )
+        .unwrap();
+        assert_eq!(ka.lcm, 60);
+    }
+
+    #[test]
+    fn item_trajectory_cycle() {
+        let monkeys = MONKEYS.iter().map(|m| m.parse().unwrap());
+        let ka = KeepAway::new(monkeys, 1).unwrap();
+
+        // an item on its own, as (holding monkey, worry level)
+        let step = |&(id, item): &(MonkeyId, WorryLevel)| {
+            let monkey = &ka.monkeys[&id];
+            let item = monkey.inspect(item) % ka.lcm;
+            (monkey.throw_target(item), item)
+        };
+
+        let cycle = Cycle::find((0, 79), step);
+        let (offset, period) = brent((0, 79), step);
+        assert_eq!((cycle.offset, cycle.period), (offset, period));
+
+        let mut state = (0, 79);
+        for steps in 0..2 * (offset + period) {
+            assert_eq!(cycle.state_at(steps), &state);
+            state = step(&state);
+        }
+
+        let steps = 1_000_000_000_000;
+        assert_eq!(
+            &fast_forward((0, 79), step, |state| *state, steps),
+            cycle.state_at(steps)
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let monkey = MONKEYS[0].replace("by 23", "by 0");
+        assert_eq!(
+            monkey.parse::<Monkey>(),
+            Err(Error::parse(4, 22, "Invalid divisor '0'"))
+        );
+
+        let monkey = MONKEYS[0].replace("old * 19", "old / 19");
+        assert_eq!(
+            monkey.parse::<Monkey>(),
+            Err(Error::parse(3, 24, "Invalid op '/'"))
+        );
+
+        let input = [MONKEYS[0], MONKEYS[1], MONKEYS[2]].join("\n\n");
+        assert_eq!(
+            DayEleven::parse(&input),
+            Err(Error::parse(6, 1, "Unknown target monkey 3"))
+        );
+    }
 }
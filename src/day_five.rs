@@ -1,65 +1,111 @@
-use lazy_static::lazy_static;
 use regex::Regex;
+use std::str::FromStr;
 
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("stack top CrateMover9000: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::{position, Error, Result};
+use crate::parse::Fields;
+use crate::regex;
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("stack top CrateMover9001: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayFive>(input)
 }
 
-pub fn part_one(input: &str) -> String {
-    let (cb, moves) = match input.split_once("\nmove") {
-        Some((cb, moves)) => (cb, "move".to_owned() + moves),
-        None => (input, "".to_owned()),
-    };
+pub fn part_one(input: &str) -> Result<String> {
+    DayFive::part_one(&DayFive::parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<String> {
+    DayFive::part_two(&DayFive::parse(input)?)
+}
 
-    let mut cargo_bay = CargoBay::new(cb);
+fn rearrange<C: CrateMover>(procedure: &Procedure) -> Result<String> {
+    let mut cargo_bay = procedure.cargo_bay.clone();
 
-    for line in moves.lines() {
-        CrateMover9000::operate_crane(&mut cargo_bay, line);
+    for (idx, mv) in procedure.moves.iter().enumerate() {
+        C::operate_crane(&mut cargo_bay, mv)
+            .map_err(|err| err.shifted(procedure.moves_line - 1 + idx))?;
     }
-    cargo_bay.stack_top()
+    Ok(cargo_bay.stack_top())
 }
 
-pub fn part_two(input: &str) -> String {
-    let (cb, moves) = match input.split_once("\nmove") {
-        Some((cb, moves)) => (cb, "move".to_owned() + moves),
-        None => (input, "".to_owned()),
-    };
+pub struct DayFive;
+
+impl Solution for DayFive {
+    const DAY: u32 = 5;
 
-    let mut cargo_bay = CargoBay::new(cb);
+    type Input = Procedure;
+    type PartOne = String;
+    type PartTwo = String;
 
-    for line in moves.lines() {
-        CrateMover9001::operate_crane(&mut cargo_bay, line);
+    fn parse(input: &str) -> Result<Procedure> {
+        let (cb, moves, moves_line) = match input.split_once("\nmove") {
+            Some((cb, moves)) => (
+                cb,
+                "move".to_owned() + moves,
+                position(input, cb.len() + 1).0,
+            ),
+            None => (input, "".to_owned(), 1),
+        };
+
+        let cargo_bay = CargoBay::new(cb)?;
+        let moves = moves
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.parse()
+                    .map_err(|err: Error| err.shifted(moves_line - 1 + idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Procedure {
+            cargo_bay,
+            moves,
+            moves_line,
+        })
+    }
+
+    fn part_one(procedure: &Procedure) -> Result<String> {
+        rearrange::<CrateMover9000>(procedure)
     }
-    cargo_bay.stack_top()
+
+    fn part_two(procedure: &Procedure) -> Result<String> {
+        rearrange::<CrateMover9001>(procedure)
+    }
+}
+
+/// The starting stacks and the moves of the crane, the first move on
+/// line `moves_line` of the input.
+#[derive(Debug, Clone)]
+pub struct Procedure {
+    cargo_bay: CargoBay,
+    moves: Vec<Move>,
+    moves_line: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct CargoBay {
     stacks: Vec<Vec<char>>,
 }
 
 impl CargoBay {
-    pub fn new(input: &str) -> Self {
-        lazy_static! {
-            static ref RE_IDX: Regex = Regex::new(r"^(\s*\d+)+").unwrap();
-            static ref RE_FIRST: Regex = Regex::new(r"(\s*)\[\w\]").unwrap();
-        }
+    pub fn new(input: &str) -> Result<Self> {
+        let re_idx = regex!(r"^(\s*\d+)+");
+        let re_first = regex!(r"(\s*)\[\w\]");
 
         let mut lines = input
             .lines()
             .rev()
-            .skip_while(|x| !RE_IDX.is_match(x))
+            .skip_while(|x| !re_idx.is_match(x))
             .skip(1)
             .peekable();
 
-        let first_line = lines.peek().expect("No stacks found in cargo bay");
+        let first_line = lines
+            .peek()
+            .ok_or_else(|| Error::parse(1, 1, "No stacks found in cargo bay"))?;
 
         // get array of whitespace between crates
-        let caps: Vec<String> = RE_FIRST
+        let caps: Vec<String> = re_first
             .captures_iter(&first_line)
             .map(|cap| cap[1].to_string())
             .collect();
@@ -89,7 +135,29 @@ impl CargoBay {
                 }
             }
         }
-        cb
+        Ok(cb)
+    }
+
+    /// Removes the top `amount` crates of a stack (1-based), bottom first
+    fn take(&mut self, stack: usize, amount: usize) -> Result<Vec<char>> {
+        let stack = self.stack_mut(stack)?;
+        let remaining = stack
+            .len()
+            .checked_sub(amount)
+            .ok_or_else(|| Error::parse(1, 1, "Not enough crates on stack"))?;
+        Ok(stack.drain(remaining..).collect())
+    }
+
+    fn put(&mut self, stack: usize, cargo: Vec<char>) -> Result<()> {
+        self.stack_mut(stack)?.extend(cargo);
+        Ok(())
+    }
+
+    fn stack_mut(&mut self, stack: usize) -> Result<&mut Vec<char>> {
+        stack
+            .checked_sub(1)
+            .and_then(|idx| self.stacks.get_mut(idx))
+            .ok_or_else(|| Error::parse(1, 1, format!("Unknown stack {}", stack)))
     }
 
     pub fn stack_top(&self) -> String {
@@ -104,47 +172,49 @@ impl CargoBay {
     }
 }
 
-trait CrateMover {
-    fn operate_crane(cb: &mut CargoBay, line: &str);
-
-    fn parse_line(line: &str) -> (usize, usize, usize) {
-        lazy_static! {
-            static ref RE_LINE: Regex =
-                Regex::new(r"move\s*(\d+)\s*from\s*(\d+)\s*to\s*(\d+)").unwrap();
-        }
-
-        let caps = RE_LINE.captures(line).unwrap();
+/// Moves `amount` crates from stack `source` to stack `target`, both
+/// 1-based
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Move {
+    amount: usize,
+    source: usize,
+    target: usize,
+}
 
-        let amount: usize = caps.get(1).unwrap().as_str().parse().unwrap();
+impl FromStr for Move {
+    type Err = Error;
 
-        let source_stack: usize = caps.get(2).unwrap().as_str().parse().unwrap();
-        let target_stack: usize = caps.get(3).unwrap().as_str().parse().unwrap();
+    fn from_str(line: &str) -> Result<Self> {
+        let re = regex!(r"move\s*(?P<amount>\d+)\s*from\s*(?P<source>\d+)\s*to\s*(?P<target>\d+)");
+        let fields = Fields::capture(re, line, "Couldn't parse move")?;
 
-        (amount, source_stack, target_stack)
+        Ok(Move {
+            amount: fields.get("amount")?,
+            source: fields.get("source")?,
+            target: fields.get("target")?,
+        })
     }
 }
 
+trait CrateMover {
+    fn operate_crane(cb: &mut CargoBay, mv: &Move) -> Result<()>;
+}
+
 struct CrateMover9000 {}
 
 impl CrateMover for CrateMover9000 {
-    fn operate_crane(cb: &mut CargoBay, line: &str) {
-        let (amount, source, target) = <CrateMover9001 as CrateMover>::parse_line(line);
-
-        let source = &mut cb.stacks[source - 1];
-        let cargo: Vec<_> = source.drain(source.len() - amount..).rev().collect();
-        cb.stacks[target - 1].extend(cargo);
+    fn operate_crane(cb: &mut CargoBay, mv: &Move) -> Result<()> {
+        let cargo: Vec<_> = cb.take(mv.source, mv.amount)?.into_iter().rev().collect();
+        cb.put(mv.target, cargo)
     }
 }
 
 struct CrateMover9001 {}
 
 impl CrateMover for CrateMover9001 {
-    fn operate_crane(cb: &mut CargoBay, line: &str) {
-        let (amount, source, target) = <CrateMover9001 as CrateMover>::parse_line(line);
-
-        let source = &mut cb.stacks[source - 1];
-        let cargo: Vec<_> = source.drain(source.len() - amount..).collect();
-        cb.stacks[target - 1].extend(cargo);
+    fn operate_crane(cb: &mut CargoBay, mv: &Move) -> Result<()> {
+        let cargo = cb.take(mv.source, mv.amount)?;
+        cb.put(mv.target, cargo)
     }
 }
 
@@ -153,6 +223,10 @@ mod tests {
     use super::*;
     use indoc::indoc;
 
+    fn operate<C: CrateMover>(cb: &mut CargoBay, line: &str) -> Result<()> {
+        C::operate_crane(cb, &line.parse()?)
+    }
+
     #[test]
     fn test_cargo_bay_creation() {
         let cargo_bay = indoc! {"
@@ -161,14 +235,14 @@ mod tests {
             [Z] [M] [P]
              1   2   3
         "};
-        let cb = CargoBay::new(cargo_bay);
+        let cb = CargoBay::new(cargo_bay).unwrap();
         assert_eq!(cb.stack_top(), "NDP");
 
         let cargo_bay = indoc! {"
             [A]
              1
         "};
-        let cb = CargoBay::new(cargo_bay);
+        let cb = CargoBay::new(cargo_bay).unwrap();
         assert_eq!(cb.stack_top(), "A");
 
         let cargo_bay = indoc! {"
@@ -176,7 +250,7 @@ mod tests {
             [Z] [M] [P] [X]
              1   2   3   4
         "};
-        let cb = CargoBay::new(cargo_bay);
+        let cb = CargoBay::new(cargo_bay).unwrap();
         assert_eq!(cb.stack_top(), "ZBTX");
     }
 
@@ -203,22 +277,22 @@ mod tests {
         let mut cb = CargoBay {
             stacks: vec![vec!['A', 'B'], vec!['C']],
         };
-        CrateMover9000::operate_crane(&mut cb, "move 1 from 1 to 2");
+        operate::<CrateMover9000>(&mut cb, "move 1 from 1 to 2").unwrap();
         assert_eq!(cb.stack_top(), "AB");
 
-        CrateMover9000::operate_crane(&mut cb, "move 2 from 2 to 1");
+        operate::<CrateMover9000>(&mut cb, "move 2 from 2 to 1").unwrap();
         assert_eq!(cb.stack_top(), "C ");
 
-        CrateMover9000::operate_crane(&mut cb, "move 3 from 1 to 2");
+        operate::<CrateMover9000>(&mut cb, "move 3 from 1 to 2").unwrap();
         assert_eq!(cb.stack_top(), " A");
 
         let mut cb = CargoBay {
             stacks: vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']],
         };
-        CrateMover9000::operate_crane(&mut cb, "move 1 from 1 to 2");
+        operate::<CrateMover9000>(&mut cb, "move 1 from 1 to 2").unwrap();
         assert_eq!(cb.stack_top(), "ZNP");
 
-        CrateMover9000::operate_crane(&mut cb, "move 3 from 2 to 3");
+        operate::<CrateMover9000>(&mut cb, "move 3 from 2 to 3").unwrap();
         assert_eq!(cb.stack_top(), "ZMC");
     }
 
@@ -227,13 +301,13 @@ mod tests {
         let mut cb = CargoBay {
             stacks: vec![vec!['A', 'B'], vec!['C']],
         };
-        CrateMover9001::operate_crane(&mut cb, "move 1 from 1 to 2");
+        operate::<CrateMover9001>(&mut cb, "move 1 from 1 to 2").unwrap();
         assert_eq!(cb.stack_top(), "AB");
 
-        CrateMover9001::operate_crane(&mut cb, "move 2 from 2 to 1");
+        operate::<CrateMover9001>(&mut cb, "move 2 from 2 to 1").unwrap();
         assert_eq!(cb.stack_top(), "B ");
 
-        CrateMover9001::operate_crane(&mut cb, "move 3 from 1 to 2");
+        operate::<CrateMover9001>(&mut cb, "move 3 from 1 to 2").unwrap();
         assert_eq!(cb.stack_top(), " B");
     }
 
@@ -242,16 +316,62 @@ mod tests {
         let mut cb = CargoBay {
             stacks: vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']],
         };
-        CrateMover9001::operate_crane(&mut cb, "move 1 from 1 to 2");
+        operate::<CrateMover9001>(&mut cb, "move 1 from 1 to 2").unwrap();
         // Z
         // M C D N
         // P
         assert_eq!(cb.stack_top(), "ZNP");
 
-        CrateMover9001::operate_crane(&mut cb, "move 3 from 2 to 3");
+        operate::<CrateMover9001>(&mut cb, "move 3 from 2 to 3").unwrap();
         // Z
         // M
         // P C D N
         assert_eq!(cb.stack_top(), "ZMN");
     }
+
+    #[test]
+    fn test_movement_invalid() {
+        let mut cb = CargoBay {
+            stacks: vec![vec!['A', 'B'], vec!['C']],
+        };
+        assert_eq!(
+            operate::<CrateMover9000>(&mut cb, "move 3 from 1 to 2"),
+            Err(Error::parse(1, 1, "Not enough crates on stack"))
+        );
+        assert_eq!(
+            operate::<CrateMover9001>(&mut cb, "move 1 from 3 to 2"),
+            Err(Error::parse(1, 1, "Unknown stack 3"))
+        );
+        assert_eq!(
+            operate::<CrateMover9001>(&mut cb, "lift 1 from 1 to 2"),
+            Err(Error::parse(1, 1, "Couldn't parse move"))
+        );
+    }
+
+    #[test]
+    fn test_rearrange_error_line() {
+        let input = indoc! {"
+            [A]
+             1
+
+            move 1 from 1 to 1
+            move 2 from 1 to 1
+        "};
+        assert_eq!(
+            DayFive::parse(input).and_then(|procedure| rearrange::<CrateMover9000>(&procedure)),
+            Err(Error::parse(5, 1, "Not enough crates on stack"))
+        );
+
+        let input = indoc! {"
+            [A]
+             1
+
+            move 1 from 1 to 1
+            move two from 1 to 1
+        "};
+        assert_eq!(
+            DayFive::parse(input).map(|_| ()),
+            Err(Error::parse(5, 1, "Couldn't parse move"))
+        );
+    }
 }
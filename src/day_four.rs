@@ -1,92 +1,127 @@
-use std::collections::HashSet;
-use std::iter::FromIterator;
-
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!(
-        "Number of fully contained assignments: {:?}",
-        part_one_solution
-    );
-
-    let part_two_solution = part_two(input);
-    println!("Number of overlapping assignments: {:?}", part_two_solution);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::interval::Interval;
+use crate::solution::{self, Solution};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayFour>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    input.lines().fold(
-        0,
-        |acc, line| {
-            if fully_contained(line) {
-                acc + 1
-            } else {
-                acc
-            }
-        },
-    )
+pub fn part_one(input: &str) -> Result<u32> {
+    DayFour::part_one(&DayFour::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    input
-        .lines()
-        .fold(0, |acc, line| if overlapping(line) { acc + 1 } else { acc })
+pub fn part_two(input: &str) -> Result<u32> {
+    DayFour::part_two(&DayFour::parse(input)?)
 }
 
-fn construct_sets(assignment: &str) -> (HashSet<u32>, HashSet<u32>) {
-    let (one, two) = assignment.split_once(',').unwrap();
+/// Section ranges of a pair of elves.
+pub type Assignment = (Interval<u64>, Interval<u64>);
+
+pub struct DayFour;
 
-    let (one_start, one_end) = one.split_once('-').unwrap();
-    let one_start: u32 = one_start.parse().unwrap();
-    let one_end: u32 = one_end.parse().unwrap();
-    let one: HashSet<u32> = HashSet::from_iter(one_start..one_end + 1);
+impl Solution for DayFour {
+    const DAY: u32 = 4;
 
-    let (two_start, two_end) = two.split_once('-').unwrap();
-    let two_start: u32 = two_start.parse().unwrap();
-    let two_end: u32 = two_end.parse().unwrap();
-    let two: HashSet<u32> = HashSet::from_iter(two_start..two_end + 1);
+    type Input = Vec<Assignment>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    (one, two)
+    fn parse(input: &str) -> Result<Vec<Assignment>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_assignment(line).map_err(|err| err.shifted(idx)))
+            .collect()
+    }
+
+    fn part_one(assignments: &Vec<Assignment>) -> Result<u32> {
+        Ok(assignments.iter().filter(|a| fully_contained(a)).count() as u32)
+    }
+
+    fn part_two(assignments: &Vec<Assignment>) -> Result<u32> {
+        Ok(assignments.iter().filter(|a| overlapping(a)).count() as u32)
+    }
 }
 
-fn fully_contained(assignment: &str) -> bool {
-    let (one, two) = construct_sets(assignment);
+fn parse_assignment(assignment: &str) -> Result<Assignment> {
+    let (one, two) = assignment
+        .split_once(',')
+        .ok_or_else(|| Error::parse(1, 1, "Missing ',' between assignments"))?;
+
+    let preceding_columns = one.len() + 1;
+    let one = one.parse()?;
+    let two = two
+        .parse()
+        .map_err(|err: Error| err.shifted_columns(preceding_columns))?;
+
+    Ok((one, two))
+}
 
-    one.is_superset(&two) || two.is_superset(&one)
+fn fully_contained((one, two): &Assignment) -> bool {
+    one.contains_interval(two) || two.contains_interval(one)
 }
 
-fn overlapping(assignment: &str) -> bool {
-    let (one, two) = construct_sets(assignment);
-    one.intersection(&two).count() != 0
+fn overlapping((one, two): &Assignment) -> bool {
+    one.overlaps(two)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn assignment(sections: &str) -> Assignment {
+        parse_assignment(sections).unwrap()
+    }
+
     #[test]
     fn test_fully_contained_false() {
-        assert_eq!(fully_contained("2-4,6-8"), false);
-        assert_eq!(fully_contained("2-3,4-5"), false);
-        assert_eq!(fully_contained("5-7,7-9"), false);
-        assert_eq!(fully_contained("2-6,4-8"), false);
+        assert!(!fully_contained(&assignment("2-4,6-8")));
+        assert!(!fully_contained(&assignment("2-3,4-5")));
+        assert!(!fully_contained(&assignment("5-7,7-9")));
+        assert!(!fully_contained(&assignment("2-6,4-8")));
     }
 
     #[test]
     fn test_fully_contained_true() {
-        assert_eq!(fully_contained("2-8,3-7"), true);
-        assert_eq!(fully_contained("6-6,4-6"), true);
+        assert!(fully_contained(&assignment("2-8,3-7")));
+        assert!(fully_contained(&assignment("6-6,4-6")));
     }
 
     #[test]
     fn test_overlapping_false() {
-        assert_eq!(overlapping("2-4,6-8"), false);
-        assert_eq!(overlapping("2-3,4-5"), false);
+        assert!(!overlapping(&assignment("2-4,6-8")));
+        assert!(!overlapping(&assignment("2-3,4-5")));
     }
 
     #[test]
     fn test_overlapping_true() {
-        assert_eq!(overlapping("5-7,7-9"), true);
-        assert_eq!(overlapping("2-8,3-7"), true);
-        assert_eq!(overlapping("6-6,4-6"), true);
-        assert_eq!(overlapping("2-6,4-8"), true);
+        assert!(overlapping(&assignment("5-7,7-9")));
+        assert!(overlapping(&assignment("2-8,3-7")));
+        assert!(overlapping(&assignment("6-6,4-6")));
+        assert!(overlapping(&assignment("2-6,4-8")));
+    }
+
+    #[test]
+    fn test_huge_sections() {
+        let sections = "1-18446744073709551615,4000000000-5000000000";
+        assert!(fully_contained(&assignment(sections)));
+        assert!(overlapping(&assignment(sections)));
+    }
+
+    #[test]
+    fn test_invalid_assignment() {
+        assert_eq!(
+            parse_assignment("2-6"),
+            Err(Error::parse(1, 1, "Missing ',' between assignments"))
+        );
+        assert_eq!(
+            parse_assignment("2-6,4_8"),
+            Err(Error::parse(1, 5, "Missing '-' in range"))
+        );
+        assert_eq!(
+            parse_assignment("12-6,4-x"),
+            Err(Error::parse(1, 8, "Couldn't parse range end"))
+        );
     }
 }
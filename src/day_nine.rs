@@ -1,42 +1,57 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::{
-    collections::HashSet,
-    fmt,
-    io::{Error, ErrorKind},
-    str::FromStr,
-};
-
-use crate::Point2D;
-
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
-
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+use std::{collections::HashSet, fmt, str::FromStr};
+
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error, Result};
+use crate::parse::Fields;
+use crate::point::{Direction, Point2D};
+use crate::regex;
+use crate::solution::{self, Solution};
+use crate::sparse_grid::{Render, SparseGrid};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayNine>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let mut field = Field::default();
-    let movements = parse_input(input);
+pub fn part_one(input: &str) -> Result<u32> {
+    DayNine::part_one(&DayNine::parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    DayNine::part_two(&DayNine::parse(input)?)
+}
+
+pub struct DayNine;
+
+impl Solution for DayNine {
+    const DAY: u32 = 9;
+
+    type Input = Vec<Move>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    for ref m in movements {
-        field.apply_move(m);
+    fn parse(input: &str) -> Result<Vec<Move>> {
+        parse_input(input)
     }
 
-    field.visited.len().try_into().unwrap()
-}
+    fn part_one(movements: &Vec<Move>) -> Result<u32> {
+        let mut field = Field::default();
 
-pub fn part_two(input: &str) -> u32 {
-    let mut field = Field::new(10);
-    let movements = parse_input(input);
+        for m in movements {
+            field.apply_move(m);
+        }
 
-    for ref m in movements {
-        field.apply_move(m);
+        Ok(field.visited.len().try_into().unwrap())
     }
 
-    field.visited.len().try_into().unwrap()
+    fn part_two(movements: &Vec<Move>) -> Result<u32> {
+        let mut field = Field::new(10);
+
+        for m in movements {
+            field.apply_move(m);
+        }
+
+        Ok(field.visited.len().try_into().unwrap())
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -48,7 +63,7 @@ enum MoveDirection {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-struct Move {
+pub struct Move {
     direction: MoveDirection,
     distance: u32,
 }
@@ -66,42 +81,14 @@ impl Default for Field {
 }
 
 impl fmt::Display for Field {
-    //     ---
-    // fld 321012345678901
-    // str 012345678901234 str  fld  neg +dif +min
-    //     3...........4..  0    4   -4    2    0
-    //  d  ...............  1    3   -3    3    1
-    //  i  ...............  2    2   -2    4    2
-    //  f  ....H1.........  3    1   -1    5    3
-    //     ...s...........  4    0    0    6    4
-    //  y  ...............  5   -1    1    7    5
-    //     .2.............  6   -2    2    8    6
-    //          dif x
-    //
-    // field_to_string:
-    //   str_x = fld_x - min_x
-    //   str_y = dif_y + min_y - fld_y
-    //
-    //   pos = str_x + str_y * dif_x
-    //
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let min_x = self.rope.iter().map(|p| p.x).min().unwrap();
-        let max_x = self.rope.iter().map(|p| p.x).max().unwrap();
-        let min_y = self.rope.iter().map(|p| p.y).min().unwrap();
-        let max_y = self.rope.iter().map(|p| p.y).max().unwrap();
-        let delta_x = max_x - min_x;
-        let delta_y = max_y - min_y;
-
-        let line = ".".repeat((delta_x + 1).try_into().unwrap()) + "\n";
-        let mut field = line.repeat((delta_y + 1).try_into().unwrap());
-
-        for r in self.rope.iter().rev() {
-            let str_x = r.x - min_x;
-            let str_y = delta_y + min_y - r.y;
-            let str_idx: usize = (str_x + str_y * (delta_x + 2)).try_into().unwrap();
-            field.replace_range(str_idx..(str_idx + 1), "x");
-        }
-        write!(f, "{}", field.trim())
+        let rope: SparseGrid<()> = self
+            .rope
+            .iter()
+            .map(|knot| (Point2D::new(knot.x.into(), knot.y.into()), ()))
+            .collect();
+        let field = rope.render(&Render::default(), |_| 'x');
+        write!(f, "{}", field.trim_end())
     }
 }
 
@@ -120,87 +107,15 @@ impl Field {
     }
 
     fn single_step(&mut self, direction: &MoveDirection) {
-        match direction {
-            MoveDirection::Up => self.rope[0].y += 1,
-            MoveDirection::Right => self.rope[0].x += 1,
-            MoveDirection::Down => self.rope[0].y -= 1,
-            MoveDirection::Left => self.rope[0].x -= 1,
-        }
+        self.rope[0] += Direction::from(direction).unit();
 
         let mut prev = self.rope[0];
 
         for r in self.rope.iter_mut().skip(1) {
-            if !prev.is_moore(&r) {
-                //   -21012
-                //  2 ppppp
-                //  1 p...p
-                //  0 p.r.p
-                // -1 p...p
-                // -2 ppppp
-                // corners are only possible when rope.len() > 2
-                match prev {
-                    Point2D { x, y } if x == r.x + 0 && y == r.y + 2 => {
-                        r.y += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 1 && y == r.y + 2 => {
-                        r.y += 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 2 && y == r.y + 2 => {
-                        r.y += 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 2 && y == r.y + 1 => {
-                        r.y += 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 2 && y == r.y + 0 => {
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 2 && y == r.y - 1 => {
-                        r.y -= 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 2 && y == r.y - 2 => {
-                        r.y -= 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 1 && y == r.y - 2 => {
-                        r.y -= 1;
-                        r.x += 1;
-                    }
-                    Point2D { x, y } if x == r.x + 0 && y == r.y - 2 => {
-                        r.y -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 1 && y == r.y - 2 => {
-                        r.y -= 1;
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 2 && y == r.y - 2 => {
-                        r.y -= 1;
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 2 && y == r.y - 1 => {
-                        r.y -= 1;
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 2 && y == r.y + 0 => {
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 2 && y == r.y + 1 => {
-                        r.y += 1;
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 2 && y == r.y + 2 => {
-                        r.y += 1;
-                        r.x -= 1;
-                    }
-                    Point2D { x, y } if x == r.x - 1 && y == r.y + 2 => {
-                        r.y += 1;
-                        r.x -= 1;
-                    }
-                    _ => unreachable!("Prev to far away"),
-                };
+            // a knot out of reach moves one step (per axis) towards the
+            // previous one, diagonally when not in line with it
+            if !prev.is_moore(r) {
+                *r += (prev - *r).signum();
             }
             prev = *r;
         }
@@ -208,49 +123,47 @@ impl Field {
     }
 }
 
-impl FromStr for Move {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Error> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(\w)\s*(\d+)").unwrap();
+impl From<&MoveDirection> for Direction {
+    fn from(direction: &MoveDirection) -> Self {
+        match direction {
+            MoveDirection::Up => Direction::North,
+            MoveDirection::Right => Direction::East,
+            MoveDirection::Down => Direction::South,
+            MoveDirection::Left => Direction::West,
         }
+    }
+}
 
-        let caps = RE.captures(s).ok_or(Self::Err::new(
-            ErrorKind::InvalidData,
-            "Couldn't parse move",
-        ))?;
+impl FromStr for Move {
+    type Err = Error;
 
-        // We now these groups are there bc. the regex matches
-        let direction = caps.get(1).unwrap().as_str();
-        let distance =
-            caps.get(2).unwrap().as_str().parse().map_err(|_| {
-                Self::Err::new(ErrorKind::InvalidData, "Couldn't parse move distance")
-            })?;
+    fn from_str(s: &str) -> Result<Self> {
+        let re = regex!(r"^(?P<direction>\w)\s*(?P<distance>\d+)");
+        let fields = Fields::capture(re, s, "Couldn't parse move")?;
 
         Ok(Self {
-            direction: match direction {
-                "U" => MoveDirection::Up,
-                "R" => MoveDirection::Right,
-                "D" => MoveDirection::Down,
-                "L" => MoveDirection::Left,
-                _ => {
-                    return Err(Self::Err::new(
-                        ErrorKind::InvalidData,
-                        "Couldn't parse move direction",
-                    ))
-                }
-            },
-            distance,
+            direction: fields.get("direction")?,
+            distance: fields.get("distance")?,
         })
     }
 }
 
-fn parse_input(input: &str) -> Vec<Move> {
-    input
-        .lines()
-        .map(|line| line.parse().expect("Can't parse input"))
-        .collect()
+impl FromStr for MoveDirection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "U" => Ok(MoveDirection::Up),
+            "R" => Ok(MoveDirection::Right),
+            "D" => Ok(MoveDirection::Down),
+            "L" => Ok(MoveDirection::Left),
+            _ => Err(Error::parse(1, 1, "Couldn't parse move direction")),
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Move>> {
+    parse_lines(input)
 }
 
 #[cfg(test)]
@@ -267,7 +180,7 @@ mod tests {
             L 3
         "};
         assert_eq!(
-            parse_input(movements),
+            parse_input(movements).unwrap(),
             vec![
                 Move {
                     direction: MoveDirection::Right,
@@ -289,6 +202,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            parse_input("R 4\nX 2\n"),
+            Err(Error::parse(2, 1, "Invalid direction 'X'"))
+        );
+        assert_eq!(
+            parse_input("R 4\nD 99999999999\n"),
+            Err(Error::parse(2, 3, "Invalid distance '99999999999'"))
+        );
+    }
+
     #[test]
     fn points_moore() {
         assert!(Point2D { x: 0, y: 0 }.is_moore(&Point2D { x: 0, y: 0 }));
@@ -366,7 +291,8 @@ mod tests {
             D 1
             L 5
             R 2
-        "});
+        "})
+        .unwrap();
 
         for ref m in movements {
             field.apply_move(m);
@@ -1,57 +1,539 @@
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Elf with most cal: {:?}", part_one_solution);
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::BufRead;
 
-    let part_two_solution = part_two(input);
-    println!("Sum of top three cal: {:?}", part_two_solution);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solution::{self, Solution};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayOne>(input)
+}
+
+pub fn part_one(input: &str) -> Result<u64> {
+    DayOne::part_one(&DayOne::parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<u64> {
+    DayOne::part_two(&DayOne::parse(input)?)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let lines = input.lines();
+/// The calories of the items a single elf carries.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Elf {
+    pub items: Vec<u32>,
+}
 
-    let mut most_cal: u32 = 0;
+impl Elf {
+    pub fn total(&self) -> u64 {
+        self.items.iter().map(|&item| u64::from(item)).sum()
+    }
+}
 
-    let mut curr_cal: u32 = 0;
+/// Problem in an inventory that [`Elves::lenient`] skips over.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Issue {
+    /// Blank line right after another one or at the start.
+    EmptyGroup {
+        line: usize,
+    },
+    NotANumber {
+        line: usize,
+        text: String,
+    },
+}
 
-    for line in lines {
-        if line == "" {
-            if curr_cal > most_cal {
-                most_cal = curr_cal;
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::EmptyGroup { line } => write!(f, "line {}: empty group", line),
+            Issue::NotANumber { line, text } => {
+                write!(f, "line {}: not a number '{}'", line, text)
             }
+        }
+    }
+}
+
+/// Reads elves one at a time from a stream, each one a group of lines
+/// with one item each, groups separated by a blank line. Groups without
+/// any items are skipped.
+pub struct Elves<R> {
+    lines: std::io::Lines<R>,
+    line: usize,
+    issues: Option<Vec<Issue>>,
+}
+
+impl<R: BufRead> Elves<R> {
+    pub fn new(reader: R) -> Self {
+        Elves {
+            lines: reader.lines(),
+            line: 0,
+            issues: None,
+        }
+    }
+
+    /// Skips lines that aren't a number instead of failing, they are
+    /// collected in [`Elves::issues`] along with the empty groups.
+    pub fn lenient(reader: R) -> Self {
+        Elves {
+            issues: Some(Vec::new()),
+            ..Elves::new(reader)
+        }
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        self.issues.as_deref().unwrap_or_default()
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf>;
+
+    fn next(&mut self) -> Option<Result<Elf>> {
+        let mut elf: Option<Elf> = None;
+        let mut skipped = false;
+
+        for line in self.lines.by_ref() {
+            self.line += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            if line.is_empty() {
+                if let Some(elf) = elf.take() {
+                    return Some(Ok(elf));
+                }
+                // a group of skipped lines was already reported
+                if let (Some(issues), false) = (&mut self.issues, skipped) {
+                    issues.push(Issue::EmptyGroup { line: self.line });
+                }
+                skipped = false;
+                continue;
+            }
+
+            match (line.parse(), &mut self.issues) {
+                (Ok(item), _) => elf.get_or_insert_with(Elf::default).items.push(item),
+                (Err(_), Some(issues)) => {
+                    issues.push(Issue::NotANumber {
+                        line: self.line,
+                        text: line,
+                    });
+                    skipped = true;
+                }
+                (Err(_), None) => {
+                    return Some(Err(Error::parse(self.line, 1, "Couldn't parse line")))
+                }
+            }
+        }
+
+        // the last group doesn't need a blank line after it
+        elf.map(Ok)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        Ok(Inventory {
+            elves: Elves::new(reader).collect::<Result<_>>()?,
+        })
+    }
+
+    /// The elf carrying the most calories with its index, the first one
+    /// of them on a tie.
+    pub fn max_elf(&self) -> Option<(usize, &Elf)> {
+        self.elves
+            .iter()
+            .enumerate()
+            .max_by_key(|(idx, elf)| (elf.total(), Reverse(*idx)))
+    }
+
+    pub fn statistics(&self) -> Option<Statistics> {
+        Statistics::new(self.elves.iter().map(Elf::total))
+    }
+
+    /// Totals of the `n` elves carrying the most calories, largest first.
+    pub fn top_k(&self, n: usize) -> Vec<u64> {
+        top_k(self.elves.iter().map(Elf::total), n)
+    }
+}
+
+/// The `n` largest totals, largest first, keeping no more than `n` of
+/// them around so it works on a stream of any length.
+pub fn top_k(totals: impl IntoIterator<Item = u64>, n: usize) -> Vec<u64> {
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for total in totals {
+        heap.push(Reverse(total));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect()
+}
+
+/// Top `n` totals of an inventory streamed from `reader` without holding
+/// on to the elves.
+pub fn top_k_from(reader: impl BufRead, n: usize) -> Result<Vec<u64>> {
+    itertools::process_results(Elves::new(reader), |elves| {
+        top_k(elves.map(|elf| elf.total()), n)
+    })
+}
+
+/// Descriptive statistics over the totals of the elves.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Statistics {
+    /// sorted, never empty
+    totals: Vec<u64>,
+}
+
+impl Statistics {
+    /// `None` without any totals.
+    pub fn new(totals: impl IntoIterator<Item = u64>) -> Option<Self> {
+        let mut totals: Vec<u64> = totals.into_iter().collect();
+        totals.sort_unstable();
+        (!totals.is_empty()).then_some(Statistics { totals })
+    }
+
+    pub fn count(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn min(&self) -> u64 {
+        self.totals[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.totals[self.totals.len() - 1]
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.totals.iter().map(|&total| total as f64).sum::<f64>() / self.count() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Interpolates linearly between the two closest totals, `p` is
+    /// clamped to `0..=100`.
+    pub fn percentile(&self, p: f64) -> f64 {
+        let rank = p.clamp(0.0, 100.0) / 100.0 * (self.count() - 1) as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        let (lower_total, upper_total) = (self.totals[lower] as f64, self.totals[upper] as f64);
+        lower_total + (upper_total - lower_total) * (rank - lower as f64)
+    }
+
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .totals
+            .iter()
+            .map(|&total| (total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.count() as f64;
+        variance.sqrt()
+    }
+
+    /// Number of elves per range of totals, the ranges split `min..=max`
+    /// into `buckets` ranges of the same size.
+    pub fn buckets(&self, buckets: usize) -> Vec<(u64, u64, usize)> {
+        let buckets = buckets.max(1) as u64;
+        let span = self.max() - self.min() + 1;
+        let size = span / buckets + u64::from(!span.is_multiple_of(buckets));
 
-            curr_cal = 0;
+        let mut counts: Vec<(u64, u64, usize)> = (0..buckets)
+            .map(|idx| self.min() + idx * size)
+            .take_while(|start| *start <= self.max())
+            .map(|start| (start, (start + size - 1).min(self.max()), 0))
+            .collect();
+        for total in &self.totals {
+            counts[((total - self.min()) / size) as usize].2 += 1;
+        }
+        counts
+    }
+
+    /// Text histogram with a line per bucket, the longest bar is `width`
+    /// characters long.
+    pub fn histogram(&self, buckets: usize, width: usize) -> String {
+        let buckets = self.buckets(buckets);
+        let most = buckets
+            .iter()
+            .map(|(_, _, count)| *count)
+            .max()
+            .unwrap_or(0);
+        let label_width = self.max().to_string().len();
+        let count_width = most.to_string().len();
+
+        buckets
+            .iter()
+            .map(|(start, end, count)| {
+                let bar = "#".repeat((count * width).div_ceil(most.max(1)));
+                let line = format!(
+                    "{:>w$}-{:>w$} | {:>c$} {}",
+                    start,
+                    end,
+                    count,
+                    bar,
+                    w = label_width,
+                    c = count_width
+                );
+                line.trim_end().to_owned()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
 
-            continue;
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Elves:   {}", self.count())?;
+        writeln!(f, "Min:     {}", self.min())?;
+        writeln!(f, "Max:     {}", self.max())?;
+        writeln!(f, "Mean:    {:.2}", self.mean())?;
+        writeln!(f, "Median:  {:.2}", self.median())?;
+        for p in [25.0, 75.0, 90.0, 99.0] {
+            writeln!(f, "{:<8} {:.2}", format!("P{}:", p), self.percentile(p))?;
         }
+        writeln!(f, "Std dev: {:.2}", self.std_dev())?;
+        writeln!(f, "\nHistogram:")?;
+        write!(f, "{}", self.histogram(HISTOGRAM_BUCKETS, HISTOGRAM_WIDTH))
+    }
+}
+
+const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Statistics of an inventory that may contain mistakes, which are
+/// reported instead of failing.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Report {
+    pub statistics: Option<Statistics>,
+    pub issues: Vec<Issue>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.statistics {
+            Some(statistics) => writeln!(f, "{}", statistics)?,
+            None => writeln!(f, "No elves in inventory")?,
+        }
+        if !self.issues.is_empty() {
+            writeln!(f, "\n{} issue(s):", self.issues.len())?;
+            for issue in &self.issues {
+                writeln!(f, "  {}", issue)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads an inventory leniently and computes the statistics of the
+/// elves that could be read, only failing if reading does.
+pub fn report(reader: impl BufRead) -> Result<Report> {
+    let mut elves = Elves::lenient(reader);
+    let totals = elves
+        .by_ref()
+        .map(|elf| elf.map(|elf| elf.total()))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Report {
+        statistics: Statistics::new(totals),
+        issues: elves.issues().to_vec(),
+    })
+}
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    const DAY: u32 = 1;
+
+    type Input = Inventory;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-        let result: u32 = line.parse().expect("Couldn't parse line");
-        curr_cal += result;
+    fn parse(input: &str) -> Result<Inventory> {
+        Inventory::from_reader(input.as_bytes())
     }
 
-    most_cal
+    fn part_one(inventory: &Inventory) -> Result<u64> {
+        inventory
+            .max_elf()
+            .map(|(_, elf)| elf.total())
+            .ok_or_else(|| Error::no_solution("No elves in inventory"))
+    }
+
+    fn part_two(inventory: &Inventory) -> Result<u64> {
+        Ok(inventory.top_k(3).iter().sum())
+    }
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let lines = input.lines();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const INVENTORY: &str = indoc! {"
+        1000
+        2000
+        3000
 
-    let mut cal_stack = [0, 0, 0, 0];
+        4000
 
-    for line in lines {
-        if line == "" {
-            cal_stack.sort();
-            cal_stack[0] = 0;
-            continue;
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000
+    "};
+
+    fn elf(items: &[u32]) -> Elf {
+        Elf {
+            items: items.to_vec(),
         }
+    }
 
-        let result: u32 = line.parse().expect("Couldn't parse line");
-        cal_stack[0] += result;
+    #[test]
+    fn parse() {
+        let inventory = DayOne::parse(INVENTORY).unwrap();
+        assert_eq!(
+            inventory.elves,
+            vec![
+                elf(&[1000, 2000, 3000]),
+                elf(&[4000]),
+                elf(&[5000, 6000]),
+                elf(&[7000, 8000, 9000]),
+                elf(&[10000]),
+            ]
+        );
     }
 
-    cal_stack.sort();
-    cal_stack[0] = 0;
+    #[test]
+    fn trailing_group() {
+        // without a newline or blank line after the last elf
+        let inventory = DayOne::parse("1\n2\n\n30000").unwrap();
+        assert_eq!(inventory.elves, vec![elf(&[1, 2]), elf(&[30000])]);
+
+        let inventory = DayOne::parse("1\n\n2\n\n").unwrap();
+        assert_eq!(inventory.elves, vec![elf(&[1]), elf(&[2])]);
+
+        assert_eq!(DayOne::parse("").unwrap().elves, vec![]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            DayOne::parse("1\n\n2\nthree\n"),
+            Err(Error::parse(4, 1, "Couldn't parse line"))
+        );
+        assert_eq!(
+            DayOne::part_one(&Inventory::default()),
+            Err(Error::no_solution("No elves in inventory"))
+        );
+    }
+
+    #[test]
+    fn max_elf() {
+        let inventory = DayOne::parse(INVENTORY).unwrap();
+        assert_eq!(inventory.max_elf(), Some((3, &elf(&[7000, 8000, 9000]))));
+
+        let inventory = DayOne::parse("5\n\n2\n3\n\n5").unwrap();
+        assert_eq!(inventory.max_elf(), Some((0, &elf(&[5]))));
+    }
 
-    let top_three = &cal_stack[1..4];
-    let top_three_sum = top_three.iter().sum::<u32>();
+    #[test]
+    fn empty_groups() {
+        let inventory = DayOne::parse("1\n\n\n2\n").unwrap();
+        assert_eq!(inventory.elves, vec![elf(&[1]), elf(&[2])]);
 
-    top_three_sum
+        let inventory = DayOne::parse("\n\n3\n\n").unwrap();
+        assert_eq!(inventory.elves, vec![elf(&[3])]);
+    }
+
+    #[test]
+    fn top() {
+        let inventory = DayOne::parse(INVENTORY).unwrap();
+        assert_eq!(inventory.top_k(3), vec![24000, 11000, 10000]);
+        assert_eq!(inventory.top_k(0), vec![]);
+        assert_eq!(inventory.top_k(10).len(), 5);
+
+        assert_eq!(top_k([3, 1, 4, 1, 5, 9, 2, 6], 4), vec![9, 6, 5, 4]);
+        assert_eq!(top_k_from(INVENTORY.as_bytes(), 2), Ok(vec![24000, 11000]));
+        assert!(top_k_from("1\nx\n".as_bytes(), 2).is_err());
+    }
+
+    #[test]
+    fn statistics() {
+        let stats = DayOne::parse(INVENTORY).unwrap().statistics().unwrap();
+        assert_eq!(stats.count(), 5);
+        assert_eq!((stats.min(), stats.max()), (4000, 24000));
+        assert_eq!(stats.mean(), 11000.0);
+        assert_eq!(stats.median(), 10000.0);
+        assert_eq!(stats.percentile(0.0), 4000.0);
+        assert_eq!(stats.percentile(25.0), 6000.0);
+        assert_eq!(stats.percentile(87.5), 17500.0);
+        assert_eq!(stats.percentile(100.0), 24000.0);
+        assert!((stats.std_dev() - 6985.700).abs() < 1e-3);
+
+        let stats = Statistics::new([3, 1, 2, 4]).unwrap();
+        assert_eq!(stats.median(), 2.5);
+        assert_eq!(Statistics::new([7]).unwrap().std_dev(), 0.0);
+        assert_eq!(Statistics::new([]), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let stats = Statistics::new([1, 2, 2, 3, 9, 10]).unwrap();
+        assert_eq!(stats.buckets(3), vec![(1, 4, 4), (5, 8, 0), (9, 10, 2)]);
+        assert_eq!(
+            stats.histogram(3, 8),
+            " 1- 4 | 4 ########\n 5- 8 | 0\n 9-10 | 2 ####"
+        );
+
+        let stats = Statistics::new([5, 5]).unwrap();
+        assert_eq!(stats.buckets(10), vec![(5, 5, 2)]);
+    }
+
+    #[test]
+    fn report_issues() {
+        let inventory = report("\n1\n2\n\n\nx\n3\n\ny\n\n4".as_bytes()).unwrap();
+        assert_eq!(
+            inventory.issues,
+            vec![
+                Issue::EmptyGroup { line: 1 },
+                Issue::EmptyGroup { line: 5 },
+                Issue::NotANumber {
+                    line: 6,
+                    text: "x".to_owned()
+                },
+                Issue::NotANumber {
+                    line: 9,
+                    text: "y".to_owned()
+                },
+            ]
+        );
+        assert_eq!(inventory.statistics, Statistics::new([3, 3, 4]));
+
+        let inventory = report("".as_bytes()).unwrap();
+        assert_eq!(inventory.statistics, None);
+        assert_eq!(inventory.to_string(), "No elves in inventory\n");
+    }
+
+    #[test]
+    fn large_totals() {
+        let inventory = DayOne::parse("4294967295\n4294967295\n").unwrap();
+        assert_eq!(DayOne::part_one(&inventory), Ok(8589934590));
+    }
 }
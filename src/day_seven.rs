@@ -1,68 +1,80 @@
-use lazy_static::lazy_static;
-use regex::Regex;
 use std::fmt;
-use std::io::{Error, ErrorKind};
 
 use std::str::FromStr;
 
 use rctree::Node;
 
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::{position, Error, Result};
+use crate::parse::Fields;
+use crate::regex;
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DaySeven>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    lazy_static! {
-        static ref RE_CMD_RAW: Regex = Regex::new(r"(?m)^\$(?:[^\$])+").unwrap();
-    }
+pub fn part_one(input: &str) -> Result<u32> {
+    DaySeven::part_one(&DaySeven::parse(input)?)
+}
 
-    let cmds: Result<Vec<_>, _> = RE_CMD_RAW
-        .find_iter(input)
-        .map(|m| parse_cmd(m.as_str()))
-        .collect();
-
-    let tree = FsTree::construct(&cmds.unwrap()).unwrap();
-    let sum: u32 = tree
-        .tree
-        .descendants()
-        .filter_map(|n| match &*n.borrow() {
-            FsObj::Directory { name: _, total } => Some(total.to_owned()),
-            FsObj::File { name: _, size: _ } => None,
-        })
-        .filter(|size| size <= &100000)
-        .sum();
-    sum
+pub fn part_two(input: &str) -> Result<u32> {
+    DaySeven::part_two(&DaySeven::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    lazy_static! {
-        static ref RE_CMD_RAW: Regex = Regex::new(r"(?m)^\$(?:[^\$])+").unwrap();
+pub struct DaySeven;
+
+impl Solution for DaySeven {
+    const DAY: u32 = 7;
+
+    type Input = FsTree;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<FsTree> {
+        let mut tree = FsTree::new();
+
+        for m in regex!(r"(?m)^\$(?:[^\$])+").find_iter(input) {
+            let preceding_lines = position(input, m.start()).0 - 1;
+            parse_cmd(m.as_str())
+                .and_then(|cmd| tree.apply(&cmd))
+                .map_err(|err| err.shifted(preceding_lines))?;
+        }
+
+        Ok(tree)
     }
 
-    let cmds: Result<Vec<_>, _> = RE_CMD_RAW
-        .find_iter(input)
-        .map(|m| parse_cmd(m.as_str()))
-        .collect();
-
-    let tree = FsTree::construct(&cmds.unwrap()).unwrap();
-    let remaining = 70000000 - tree.total_size();
-    let to_free = 30000000 - remaining;
-
-    let smallest_viable: u32 = tree
-        .tree
-        .descendants()
-        .filter_map(|n| match &*n.borrow() {
-            FsObj::Directory { name: _, total } => Some(total.to_owned()),
-            FsObj::File { name: _, size: _ } => None,
-        })
-        .filter(|size| size >= &to_free)
-        .min()
-        .unwrap();
-    smallest_viable
+    fn part_one(tree: &FsTree) -> Result<u32> {
+        let sum: u32 = tree
+            .tree
+            .descendants()
+            .filter_map(|n| match &*n.borrow() {
+                FsObj::Directory { name: _, total } => Some(total.to_owned()),
+                FsObj::File { name: _, size: _ } => None,
+            })
+            .filter(|size| size <= &100000)
+            .sum();
+        Ok(sum)
+    }
+
+    fn part_two(tree: &FsTree) -> Result<u32> {
+        let remaining = 70000000_u32
+            .checked_sub(tree.total_size())
+            .ok_or_else(|| Error::no_solution("Files exceed the disk space"))?;
+        let to_free = 30000000_u32.saturating_sub(remaining);
+
+        let smallest_viable: u32 = tree
+            .tree
+            .descendants()
+            .filter_map(|n| match &*n.borrow() {
+                FsObj::Directory { name: _, total } => Some(total.to_owned()),
+                FsObj::File { name: _, size: _ } => None,
+            })
+            .filter(|size| size >= &to_free)
+            .min()
+            .ok_or_else(|| Error::no_solution("No directory frees enough space"))?;
+        Ok(smallest_viable)
+    }
 }
 
 fn parse_args(input: &str) -> Vec<&str> {
@@ -75,32 +87,27 @@ enum Cmd<'a> {
     Ls { output: Vec<&'a str> },
 }
 
-fn parse_cmd(input: &str) -> Result<Cmd, &'static str> {
-    lazy_static! {
-        static ref RE_CMD: Regex = Regex::new(r"^\$ ([[:word:]]+)((?:\s+*\S+)*)\s*").unwrap();
-    }
-
+/// Parses a command with its output, errors are positioned relative to
+/// the command line.
+fn parse_cmd(input: &str) -> Result<Cmd<'_>> {
     let mut lines = input.lines();
+    let line = lines
+        .next()
+        .ok_or_else(|| Error::parse(1, 1, "Input does not contain a line"))?;
 
-    let caps = RE_CMD
-        .captures(lines.next().ok_or("Input does not contain a line")?)
-        .ok_or("No cmd found")?;
+    let re = regex!(r"^\$ (?P<cmd>[[:word:]]+)(?P<args>(?:\s+*\S+)*)\s*");
+    let fields = Fields::capture(re, line, "No cmd found")?;
 
-    // we know that the first group is not None bc. whole regex matches
-    let cmd = match caps.get(1).unwrap().as_str() {
-        "ls" => "ls",
-        "cd" => "cd",
-        _ => return Err("Unknown cmd"),
-    };
-
-    let args = match caps.get(2) {
-        Some(args_str) => parse_args(args_str.as_str()),
-        None => Vec::new(),
+    let cmd = match fields.text("cmd") {
+        Some("ls") => "ls",
+        Some("cd") => "cd",
+        _ => return Err(fields.error("cmd", "Unknown cmd")),
     };
+    let args = parse_args(fields.text("args").unwrap_or_default());
 
     if cmd == "ls" {
         if args.len() != 0 {
-            return Err("ls doesn't take any args");
+            return Err(fields.error("args", "ls doesn't take any args"));
         }
         return Ok(Cmd::Ls {
             output: lines.collect(),
@@ -109,13 +116,13 @@ fn parse_cmd(input: &str) -> Result<Cmd, &'static str> {
 
     if cmd == "cd" {
         if args.len() != 1 {
-            return Err("cd takes exactly one argument");
+            return Err(fields.error("args", "cd takes exactly one argument"));
         }
 
         return Ok(Cmd::Cd { path: args[0] });
     }
 
-    Err("Unable to parse cmd")
+    Err(Error::parse(1, 1, "Unable to parse cmd"))
 }
 
 #[derive(Debug, PartialEq)]
@@ -127,31 +134,18 @@ enum FsObj {
 impl FromStr for FsObj {
     type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Error> {
-        lazy_static! {
-            static ref RE_LINE: Regex = Regex::new(r"([0-9]+|dir) ([[:print:]]+)").unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let re = regex!(r"(?P<size>[0-9]+|dir) (?P<name>[[:print:]]+)");
+        let fields = Fields::capture(re, s, "Couldn't parse output")?;
+
+        let name = fields.get("name")?;
+        match fields.text("size") {
+            Some("dir") => Ok(FsObj::Directory { name, total: 0 }),
+            _ => Ok(FsObj::File {
+                name,
+                size: fields.get("size")?,
+            }),
         }
-
-        let caps = RE_LINE.captures(s).ok_or(Self::Err::new(
-            ErrorKind::InvalidData,
-            "Couldn't parse output",
-        ))?;
-
-        let fname = caps.get(2).unwrap().as_str().to_string();
-
-        let fs_obj = match caps.get(1).unwrap().as_str() {
-            "dir" => FsObj::Directory {
-                name: fname,
-                total: 0,
-            },
-            fsize => FsObj::File {
-                name: fname,
-                size: fsize
-                    .parse()
-                    .map_err(|_| Self::Err::new(ErrorKind::InvalidData, "Couldn't parse output"))?,
-            },
-        };
-        Ok(fs_obj)
     }
 }
 
@@ -169,42 +163,57 @@ impl fmt::Display for FsObj {
 type FsNode = Node<FsObj>;
 
 #[derive(Debug)]
-struct FsTree {
-    pub(crate) tree: FsNode,
+pub struct FsTree {
+    tree: FsNode,
+    cwd: FsNode,
 }
 
 impl FsTree {
-    fn construct(cmds: &Vec<Cmd>) -> Result<FsTree, Error> {
+    fn new() -> Self {
         let root = Node::new(FsObj::Directory {
             name: "/".to_string(),
             total: 0,
         });
 
-        let mut cwd = root.clone();
-        let mut cmds_iter = cmds.iter();
+        FsTree {
+            cwd: root.clone(),
+            tree: root,
+        }
+    }
 
-        while let Some(cmd) = cmds_iter.next() {
-            cwd = match cmd {
-                Cmd::Ls { output } => {
-                    for l in output {
-                        Self::append_obj(&cwd, l.parse()?);
-                    }
-                    cwd
-                }
-                Cmd::Cd { path } if path == &".." => cwd.ancestors().take(2).last().unwrap(),
-                Cmd::Cd { path } if path == &"/" => cwd.ancestors().last().unwrap(),
-                Cmd::Cd { path } => cwd
-                    .children()
-                    .filter(|node| match &*node.borrow() {
-                        FsObj::Directory { name, total: _ } => &name == path,
-                        FsObj::File { name: _, size: _ } => false,
-                    })
-                    .next()
-                    .expect("Illegal file operation"),
-            }
+    #[cfg(test)]
+    fn construct(cmds: &Vec<Cmd>) -> Result<FsTree> {
+        let mut tree = FsTree::new();
+
+        for cmd in cmds {
+            tree.apply(cmd)?;
         }
 
-        Ok(FsTree { tree: root })
+        Ok(tree)
+    }
+
+    /// Applies a command, errors are positioned relative to the command line.
+    fn apply(&mut self, cmd: &Cmd) -> Result<()> {
+        self.cwd = match cmd {
+            Cmd::Ls { output } => {
+                for (idx, l) in output.iter().enumerate() {
+                    let obj = l.parse().map_err(|err: Error| err.shifted(idx + 1))?;
+                    Self::append_obj(&self.cwd, obj);
+                }
+                self.cwd.clone()
+            }
+            Cmd::Cd { path } if path == &".." => self.cwd.ancestors().take(2).last().unwrap(),
+            Cmd::Cd { path } if path == &"/" => self.cwd.ancestors().last().unwrap(),
+            Cmd::Cd { path } => self
+                .cwd
+                .children()
+                .find(|node| match &*node.borrow() {
+                    FsObj::Directory { name, total: _ } => name == path,
+                    FsObj::File { name: _, size: _ } => false,
+                })
+                .ok_or_else(|| Error::parse(1, 1, format!("No directory named {}", path)))?,
+        };
+        Ok(())
     }
 
     fn total_size(&self) -> u32 {
@@ -283,13 +292,16 @@ mod tests {
     #[test]
     fn test_parse_cmd_invalid() {
         let input = "$ foo /";
-        assert_eq!(parse_cmd(input), Err("Unknown cmd"));
+        assert_eq!(parse_cmd(input), Err(Error::parse(1, 3, "Unknown cmd")));
     }
 
     #[test]
     fn test_parse_cmd_ls_with_args() {
         let input = "$ ls /";
-        assert_eq!(parse_cmd(input), Err("ls doesn't take any args"));
+        assert_eq!(
+            parse_cmd(input),
+            Err(Error::parse(1, 5, "ls doesn't take any args"))
+        );
         let input = "$ ls   ";
         assert_eq!(parse_cmd(input), Ok(Cmd::Ls { output: vec![] }));
     }
@@ -297,11 +309,20 @@ mod tests {
     #[test]
     fn test_parse_cmd_cd_with_too_few_or_many_args() {
         let input = "$ cd";
-        assert_eq!(parse_cmd(input), Err("cd takes exactly one argument"));
+        assert_eq!(
+            parse_cmd(input),
+            Err(Error::parse(1, 5, "cd takes exactly one argument"))
+        );
         let input = "$ cd      ";
-        assert_eq!(parse_cmd(input), Err("cd takes exactly one argument"));
+        assert_eq!(
+            parse_cmd(input),
+            Err(Error::parse(1, 5, "cd takes exactly one argument"))
+        );
         let input = "$ cd / ..   ";
-        assert_eq!(parse_cmd(input), Err("cd takes exactly one argument"));
+        assert_eq!(
+            parse_cmd(input),
+            Err(Error::parse(1, 5, "cd takes exactly one argument"))
+        );
     }
 
     #[test]
@@ -404,4 +425,30 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_error_line() {
+        let input = indoc! {"
+            $ cd /
+            $ ls
+            dir a
+            12 b.txt
+            $ cd c
+        "};
+        assert_eq!(
+            DaySeven::parse(input).map(|_| ()),
+            Err(Error::parse(5, 1, "No directory named c"))
+        );
+
+        let input = indoc! {"
+            $ cd /
+            $ ls
+            dir a
+            12b.txt
+        "};
+        assert_eq!(
+            DaySeven::parse(input).map(|_| ()),
+            Err(Error::parse(4, 1, "Couldn't parse output"))
+        );
+    }
 }
@@ -1,35 +1,58 @@
 use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DaySix>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    packet_start_pos(input).unwrap()
+pub fn part_one(input: &str) -> Result<u32> {
+    DaySix::part_one(&DaySix::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    packet_start_pos_long(input, 14).unwrap()
+pub fn part_two(input: &str) -> Result<u32> {
+    DaySix::part_two(&DaySix::parse(input)?)
 }
 
-fn packet_start_pos(stream: &str) -> Option<u32> {
+pub struct DaySix;
+
+impl Solution for DaySix {
+    const DAY: u32 = 6;
+
+    type Input = Vec<char>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Vec<char>> {
+        Ok(input.trim_end().chars().collect())
+    }
+
+    fn part_one(stream: &Vec<char>) -> Result<u32> {
+        packet_start_pos(stream).ok_or_else(|| Error::no_solution("No start-of-packet marker"))
+    }
+
+    fn part_two(stream: &Vec<char>) -> Result<u32> {
+        packet_start_pos_long(stream, 14)
+            .ok_or_else(|| Error::no_solution("No start-of-message marker"))
+    }
+}
+
+fn packet_start_pos(stream: &[char]) -> Option<u32> {
     stream
-        .chars()
+        .iter()
         .tuple_windows::<(_, _, _, _)>()
-        .map(|(a, b, c, d)| pairwise_distinct(&a, &b, &c, &d))
+        .map(|(a, b, c, d)| pairwise_distinct(a, b, c, d))
         .enumerate()
         .skip_while(|(_, unique)| !unique)
         .map(|(idx, _)| (idx + 4) as u32) // change when TryFrom is stable
         .next()
 }
 
-fn packet_start_pos_long(stream: &str, window_size: usize) -> Option<u32> {
-    let mut stream_chars = stream.chars();
+fn packet_start_pos_long(stream: &[char], window_size: usize) -> Option<u32> {
+    let mut stream_chars = stream.iter().copied();
     let mut window: Vec<char> = Vec::new();
 
     while window.len() < (window_size - 1) {
@@ -66,26 +89,30 @@ fn pairwise_distinct_long(v: &Vec<char>) -> bool {
 mod tests {
     use super::*;
 
+    fn signal(stream: &str) -> Vec<char> {
+        stream.chars().collect()
+    }
+
     #[test]
     fn test_packet_start_window_too_long() {
-        let stream = "abc";
+        let stream = &signal("abc");
         assert_eq!(packet_start_pos_long(stream, 4), None);
         assert_eq!(packet_start_pos_long(stream, 20), None);
     }
 
     #[test]
     fn test_packet_start_none_found() {
-        let stream = "abcabc";
+        let stream = &signal("abcabc");
         assert_eq!(packet_start_pos_long(stream, 4), None);
-        let stream = "abababababababababababababababab";
+        let stream = &signal("abababababababababababababababab");
         assert_eq!(packet_start_pos_long(stream, 20), None);
     }
 
     #[test]
     fn test_packet_start_found() {
-        let stream = "abcabc";
+        let stream = &signal("abcabc");
         assert_eq!(packet_start_pos_long(stream, 3), Some(3));
-        let stream = "aaabcdefghijklmnopqrstuvwxyz";
+        let stream = &signal("aaabcdefghijklmnopqrstuvwxyz");
         assert_eq!(packet_start_pos_long(stream, 20), Some(22));
     }
 
@@ -1,93 +1,92 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::{
-    collections::VecDeque,
-    io::{Error, ErrorKind},
-    str::FromStr,
-};
-
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
-
-    let part_two_solution = part_two(input);
-    println!("Part two:");
-    part_two_solution.lines().for_each(|l| println!("{}", l));
+use std::{collections::VecDeque, str::FromStr};
+
+use crate::answer::Answer;
+use crate::error::{parse_lines, Error, Result};
+use crate::parse::Fields;
+use crate::regex;
+use crate::solution::{self, Solution};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayTen>(input)
 }
 
-pub fn part_one(input: &str) -> i32 {
-    let program = parse_program(input);
-    let mut cpu = CPU::default();
-    cpu.load(&program);
+pub fn part_one(input: &str) -> Result<i32> {
+    DayTen::part_one(&DayTen::parse(input)?)
+}
 
-    let stops = [20, 60, 100, 140, 180, 220];
-    let mut signal_strengths = 0;
+pub fn part_two(input: &str) -> Result<String> {
+    Ok(draw(&DayTen::parse(input)?))
+}
 
-    for stop in stops {
-        for _ in cpu.cycles..stop {
-            cpu.tick();
+pub struct DayTen;
+
+impl Solution for DayTen {
+    const DAY: u32 = 10;
+
+    type Input = Program;
+    type PartOne = i32;
+    type PartTwo = Answer;
+
+    fn parse(input: &str) -> Result<Program> {
+        parse_program(input)
+    }
+
+    fn part_one(program: &Program) -> Result<i32> {
+        let mut cpu = CPU::default();
+        cpu.load(program);
+
+        let stops = [20, 60, 100, 140, 180, 220];
+        let mut signal_strengths = 0;
+
+        for stop in stops {
+            for _ in cpu.cycles..stop {
+                cpu.tick();
+            }
+            signal_strengths += cpu.reg_x * stop as i32;
         }
-        signal_strengths += cpu.reg_x * stop as i32;
+        Ok(signal_strengths)
+    }
+
+    fn part_two(program: &Program) -> Result<Answer> {
+        Ok(Answer::Image(draw(program)))
     }
-    signal_strengths
 }
 
-pub fn part_two(input: &str) -> String {
-    let program = parse_program(input);
+fn draw(program: &Program) -> String {
     let mut cpu = CPU::default();
-    cpu.load(&program);
+    cpu.load(program);
 
     crt(&mut cpu)
 }
 
 #[derive(PartialEq, Debug, Clone)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(i32),
 }
 
-type Program = Vec<Instruction>;
+pub type Program = Vec<Instruction>;
 
 impl FromStr for Instruction {
     type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Error> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(\w+)(?:\s*(-?\d+))?").unwrap();
+    fn from_str(s: &str) -> Result<Self> {
+        let re = regex!(r"^(?P<name>\w+)(?:\s*(?P<value>-?\d+))?");
+        let fields = Fields::capture(re, s, "Couldn't parse instruction")?;
+
+        match fields.text("name") {
+            Some("addx") => match fields.get_opt("value")? {
+                Some(value) => Ok(Instruction::Addx(value)),
+                None => Err(Error::parse(1, s.len() + 1, "Addx needs a value")),
+            },
+            Some("noop") => Ok(Instruction::Noop),
+            _ => Err(fields.error("name", "Unknown instruction")),
         }
-
-        let caps = RE.captures(s).ok_or(Self::Err::new(
-            std::io::ErrorKind::InvalidData,
-            "Couldn't parse instruction",
-        ))?;
-
-        // We now that the first group is there bc. the regex matches
-        Ok(match caps.get(1).unwrap().as_str() {
-            "addx" => Instruction::Addx(
-                caps.get(2)
-                    .ok_or(Self::Err::new(ErrorKind::InvalidData, "Addx needs a value"))?
-                    .as_str()
-                    .parse()
-                    .map_err(|_| {
-                        Self::Err::new(ErrorKind::InvalidData, "Couldn't parse addx value")
-                    })?,
-            ),
-            "noop" => Instruction::Noop,
-            _ => {
-                return Err(Self::Err::new(
-                    ErrorKind::InvalidData,
-                    "Unknown instruction",
-                ))
-            }
-        })
     }
 }
 
-fn parse_program(input: &str) -> Program {
-    input
-        .lines()
-        .map(|line| line.parse().expect("Can't parse input"))
-        .collect()
+fn parse_program(input: &str) -> Result<Program> {
+    parse_lines(input)
 }
 
 #[derive(Debug)]
@@ -174,7 +173,7 @@ mod tests {
             addx -5
         "};
         assert_eq!(
-            parse_program(program),
+            parse_program(program).unwrap(),
             vec![
                 Instruction::Noop,
                 Instruction::Addx(3),
@@ -183,6 +182,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            parse_program("noop\naddx\n"),
+            Err(Error::parse(2, 5, "Addx needs a value"))
+        );
+        assert_eq!(
+            parse_program("noop\nnoop\nsubx 3\n"),
+            Err(Error::parse(3, 1, "Unknown instruction"))
+        );
+    }
+
     #[test]
     fn tick() {
         let program = vec![
@@ -1,41 +1,71 @@
-use itertools::Itertools;
 use std::collections::HashSet;
 use std::convert::TryInto;
 
-pub fn both(input: &str) {
-    part_one(input);
-    part_two(input);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solution::{self, Solution};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayThree>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let prio_sum: u32 = input
-        .lines()
-        .map(|l| priority(Rucksack::new(l).first_duplicate().unwrap()))
-        .sum();
+pub fn part_one(input: &str) -> Result<u32> {
+    DayThree::part_one(&DayThree::parse(input)?)
+}
 
-    println!("Sum of prios of duplicates: {:?}", prio_sum);
-    prio_sum
+pub fn part_two(input: &str) -> Result<u32> {
+    DayThree::part_two(&DayThree::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let mut badge_prio_sum = 0;
-    for group in input.lines().chunks(3).into_iter() {
-        let eg = ElfGroup {
-            elves: group
-                .map(|l| Rucksack::new(l))
-                .collect::<Vec<Rucksack>>()
-                .try_into()
-                .unwrap(),
-        };
-        badge_prio_sum += priority(eg.badge().unwrap());
+pub struct DayThree;
+
+impl Solution for DayThree {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Rucksack>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Vec<Rucksack>> {
+        Ok(input.lines().map(Rucksack::new).collect())
     }
 
-    println!("Sum of prios of badges: {:?}", badge_prio_sum);
-    badge_prio_sum
+    fn part_one(rucksacks: &Vec<Rucksack>) -> Result<u32> {
+        rucksacks
+            .iter()
+            .enumerate()
+            .map(|(idx, rucksack)| {
+                rucksack
+                    .first_duplicate()
+                    .map(priority)
+                    .ok_or_else(|| Error::parse(idx + 1, 1, "No item in both compartments"))
+            })
+            .sum()
+    }
+
+    fn part_two(rucksacks: &Vec<Rucksack>) -> Result<u32> {
+        let mut badge_prio_sum = 0;
+        for (group_idx, group) in rucksacks.chunks(3).enumerate() {
+            let line = group_idx * 3 + 1;
+            let eg = ElfGroup {
+                elves: group
+                    .to_vec()
+                    .try_into()
+                    .map_err(|_| Error::parse(line, 1, "Incomplete group of three elves"))?,
+            };
+            let badge = eg
+                .badge()
+                .ok_or_else(|| Error::parse(line, 1, "No badge shared by group"))?;
+            badge_prio_sum += priority(badge);
+        }
+
+        Ok(badge_prio_sum)
+    }
 }
 
+/// Items of a rucksack, split into its two compartments.
 #[derive(Debug, Clone)]
-struct Rucksack {
+pub struct Rucksack {
     first_comp: String,
     second_comp: String,
 }
@@ -1,17 +1,104 @@
-pub fn both(input: &str) {
-    let part_one_solution = part_one(input);
-    println!("Part one: {:?}", part_one_solution);
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::grid::{cell_error, Grid};
+use crate::point::Point2D;
+use crate::search;
+use crate::solution::{self, Solution};
 
-    let part_two_solution = part_two(input);
-    println!("Part two: {:?}", part_two_solution);
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayTwelve>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    todo!();
+pub fn part_one(input: &str) -> Result<u32> {
+    DayTwelve::part_one(&DayTwelve::parse(input)?)
 }
 
-pub fn part_two(input: &str) -> u32 {
-    todo!();
+pub fn part_two(input: &str) -> Result<u32> {
+    DayTwelve::part_two(&DayTwelve::parse(input)?)
+}
+
+pub struct DayTwelve;
+
+impl Solution for DayTwelve {
+    const DAY: u32 = 12;
+
+    type Input = Heightmap;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Heightmap> {
+        parse_heightmap(input)
+    }
+
+    fn part_one(map: &Heightmap) -> Result<u32> {
+        fewest_steps(map, [map.start])
+            .ok_or_else(|| Error::no_solution("No path from the start to the best signal"))
+    }
+
+    fn part_two(map: &Heightmap) -> Result<u32> {
+        let lowest = map
+            .heights
+            .iter()
+            .filter(|(_, height)| **height == 0)
+            .map(|(pos, _)| pos);
+        fewest_steps(map, lowest)
+            .ok_or_else(|| Error::no_solution("No path from any lowest square to the best signal"))
+    }
+}
+
+/// Heights from `a` as 0 to `z` as 25.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Heightmap {
+    heights: Grid<u8>,
+    start: Point2D<usize>,
+    end: Point2D<usize>,
+}
+
+fn parse_heightmap(input: &str) -> Result<Heightmap> {
+    let mut start = None;
+    let mut end = None;
+
+    let heights = Grid::parse(input, |pos, chr| {
+        let marker = match chr {
+            'S' => Some((&mut start, 'a')),
+            'E' => Some((&mut end, 'z')),
+            _ => None,
+        };
+        let height = match marker {
+            Some((Some(_), _)) => return Err(cell_error(pos, "Duplicate marker")),
+            Some((marker, height)) => {
+                *marker = Some(pos);
+                height
+            }
+            None => chr,
+        };
+
+        if !height.is_ascii_lowercase() {
+            return Err(cell_error(pos, "Unsupported symbol"));
+        }
+        Ok(height as u8 - b'a')
+    })?;
+
+    Ok(Heightmap {
+        heights,
+        start: start.ok_or_else(|| Error::parse(1, 1, "No start marker"))?,
+        end: end.ok_or_else(|| Error::parse(1, 1, "No end marker"))?,
+    })
+}
+
+/// Fewest steps from any of `starts` to the end, climbing at most one
+/// level per step.
+fn fewest_steps(map: &Heightmap, starts: impl IntoIterator<Item = Point2D<usize>>) -> Option<u32> {
+    let heights = &map.heights;
+    let climbable = |pos: &Point2D<usize>| {
+        let height = heights[*pos];
+        heights
+            .neighbors(*pos)
+            .filter(move |next| heights[*next] <= height + 1)
+    };
+
+    let search = search::bfs(starts, climbable, |pos| *pos == map.end);
+    search.cost()?.try_into().ok()
 }
 
 #[cfg(test)]
@@ -28,7 +115,43 @@ mod tests {
     "};
 
     #[test]
-    fn foo() {
-        todo!();
+    fn parse() {
+        let map = parse_heightmap(HEIGHTMAP).unwrap();
+        assert_eq!((map.heights.rows(), map.heights.cols()), (5, 8));
+        assert_eq!(map.start, Point2D::new(0, 0));
+        assert_eq!(map.end, Point2D::new(5, 2));
+        assert_eq!(map.heights[map.start], 0);
+        assert_eq!(map.heights[map.end], 25);
+        assert_eq!(map.heights[Point2D::new(3, 1)], b'r' - b'a');
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            parse_heightmap("Sab\nabE\nab\n"),
+            Err(Error::parse(3, 3, "Rows of different length"))
+        );
+        assert_eq!(
+            parse_heightmap("SaE\naS1\n"),
+            Err(Error::parse(2, 2, "Duplicate marker"))
+        );
+        assert_eq!(
+            parse_heightmap("SaE\nab1\n"),
+            Err(Error::parse(2, 3, "Unsupported symbol"))
+        );
+        assert_eq!(
+            parse_heightmap("Sab\n"),
+            Err(Error::parse(1, 1, "No end marker"))
+        );
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let map = parse_heightmap("SazE\n").unwrap();
+        assert_eq!(fewest_steps(&map, [map.start]), None);
+        assert!(matches!(
+            DayTwelve::part_one(&map),
+            Err(Error::NoSolution(_))
+        ));
     }
 }
@@ -1,161 +1,565 @@
-pub fn both(input: &str) {
-    part_one(input);
-    part_two(input);
+use itertools::Itertools;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parse::Fields;
+use crate::regex;
+use crate::solution::{self, Solution};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {
+    solution::both::<DayTwo>(input)
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let guide: Vec<Game> = input.lines().map(parse_line).collect();
-    let points = strategy_guide(&guide);
+pub fn part_one(input: &str) -> Result<u32> {
+    DayTwo::part_one(&DayTwo::parse(input)?)
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    DayTwo::part_two(&DayTwo::parse(input)?)
+}
 
-    println!("Total score of guide: {:?}", points);
-    points
+/// Tries every mapping of X, Y and Z to shapes and returns the one
+/// scoring the most with its score, the first one of them on a tie.
+pub fn best_mapping(input: &str) -> Result<(ShapeMapping, u32)> {
+    let rules = classic_rules();
+    let rounds = DayTwo::parse(input)?;
+    let mut best: Option<(ShapeMapping, u32)> = None;
+    for plays in Play::ALL.into_iter().permutations(3) {
+        let mapping = ShapeMapping::from([plays[0], plays[1], plays[2]]);
+        let score = strategy_guide(rules, &decode_guide(rules, &rounds, &mapping)?);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((mapping, score));
+        }
+    }
+    Ok(best.unwrap())
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let guide: Vec<Game> = input.lines().map(parse_line_v2).collect();
-    let points = strategy_guide(&guide);
+pub struct DayTwo;
+
+impl Solution for DayTwo {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Round>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    /// Leaves the second column encoded, it means something else in
+    /// either part.
+    fn parse(input: &str) -> Result<Vec<Round>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let round = parse_round(classic_rules(), line).map_err(|err| err.shifted(idx))?;
+                if !"XYZ".contains(round.column) {
+                    return Err(Error::parse(idx + 1, 3, "Invalid char in game"));
+                }
+                Ok(round)
+            })
+            .collect()
+    }
+
+    fn part_one(rounds: &Vec<Round>) -> Result<u32> {
+        let rules = classic_rules();
+        let guide = decode_guide(rules, rounds, &ShapeMapping::default())?;
+        Ok(strategy_guide(rules, &guide))
+    }
 
-    println!("Total score of guide v2: {:?}", points);
-    points
+    fn part_two(rounds: &Vec<Round>) -> Result<u32> {
+        let rules = classic_rules();
+        let guide = decode_guide(rules, rounds, &OutcomeDecoder)?;
+        Ok(strategy_guide(rules, &guide))
+    }
+}
+
+/// How a single round ends for the player.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
 }
 
-#[derive(Debug, PartialEq)]
-enum Play {
+/// Index of a shape in its [`Rules`].
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub struct Shape(pub usize);
+
+/// The shapes of the classic rules, `Rules::default()`, as a type of
+/// their own.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Play {
     Rock,
     Paper,
     Scissors,
 }
 
-#[derive(Debug, PartialEq)]
-struct Game {
-    opponent: Play,
-    player: Play,
+impl Play {
+    pub const ALL: [Play; 3] = [Play::Rock, Play::Paper, Play::Scissors];
+
+    /// Shape of the play in the classic rules.
+    pub fn shape(self) -> Shape {
+        Shape(self as usize)
+    }
+
+    /// `None` for a shape the classic rules don't have.
+    pub fn from_shape(shape: Shape) -> Option<Play> {
+        Play::ALL.get(shape.0).copied()
+    }
+
+    /// Shape to show against `opponent` to get `outcome`.
+    pub fn for_outcome(opponent: Play, outcome: Outcome) -> Play {
+        classic_rules()
+            .for_outcome(opponent.shape(), outcome)
+            .and_then(Play::from_shape)
+            .unwrap()
+    }
+}
+
+impl From<Play> for Shape {
+    fn from(play: Play) -> Self {
+        play.shape()
+    }
 }
 
-fn parse_line(line: &str) -> Game {
-    let opponent_char = line.chars().nth(0).expect("Invalid game");
-    let opponent = match opponent_char {
-        'A' => Play::Rock,
-        'B' => Play::Paper,
-        'C' => Play::Scissors,
-        _ => panic!("Invalid char in game"),
-    };
+impl fmt::Display for Play {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(classic_rules().name(self.shape()))
+    }
+}
 
-    let player_char = line.chars().nth(2).expect("Invalid game");
-    let player = match player_char {
-        'X' => Play::Rock,
-        'Y' => Play::Paper,
-        'Z' => Play::Scissors,
-        _ => panic!("Invalid char in game"),
-    };
+/// A game of hand shapes: which shapes there are, which one beats which
+/// and how many points each shape and outcome is worth.
+///
+/// Rules are written as a table with a line per shape, its score and
+/// the shapes it beats, plus an optional line with the scores of a
+/// loss, draw and win (`0 3 6` if left out):
+///
+/// ```text
+/// outcomes: 0 3 6
+/// rock 1 beats scissors
+/// paper 2 beats rock
+/// scissors 3 beats paper
+/// ```
+///
+/// Of every two different shapes exactly one has to beat the other.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rules {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[a][b]` is whether shape `a` beats shape `b`
+    beats: Vec<Vec<bool>>,
+    loss_score: u32,
+    draw_score: u32,
+    win_score: u32,
+}
+
+const CLASSIC: &str = "\
+outcomes: 0 3 6
+rock 1 beats scissors
+paper 2 beats rock
+scissors 3 beats paper
+";
+
+const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = "\
+rock 1 beats scissors, lizard
+paper 2 beats rock, spock
+scissors 3 beats paper, lizard
+lizard 4 beats spock, paper
+spock 5 beats scissors, rock
+";
+
+impl Rules {
+    /// Rock, paper, scissors as played in the puzzle.
+    pub fn classic() -> Self {
+        CLASSIC.parse().unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        ROCK_PAPER_SCISSORS_LIZARD_SPOCK.parse().unwrap()
+    }
+
+    /// Game in which every shape beats the half of the shapes listed
+    /// before it, wrapping around, e.g. rock, paper, scissors. Shapes
+    /// score their 1-based position. Needs an odd number of shapes for
+    /// every game to have a winner.
+    pub fn cyclic(names: &[&str]) -> Option<Self> {
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return None;
+        }
+
+        let beats = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| (1..=n / 2).any(|d| (b + d) % n == a))
+                    .collect()
+            })
+            .collect();
+        Some(Rules {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            scores: (1..=n as u32).collect(),
+            beats,
+            loss_score: 0,
+            draw_score: 3,
+            win_score: 6,
+        })
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.names.len()).map(Shape)
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|n| n == name).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn beats(&self, shape: Shape, other: Shape) -> bool {
+        self.beats[shape.0][other.0]
+    }
+
+    /// Shapes that `shape` beats.
+    pub fn beaten_by(&self, shape: Shape) -> impl Iterator<Item = Shape> + '_ {
+        self.shapes().filter(move |other| self.beats(shape, *other))
+    }
+
+    /// Shapes beating `shape`.
+    pub fn beating(&self, shape: Shape) -> impl Iterator<Item = Shape> + '_ {
+        self.shapes().filter(move |other| self.beats(*other, shape))
+    }
+
+    pub fn outcome(&self, player: Shape, opponent: Shape) -> Outcome {
+        if self.beats(player, opponent) {
+            Outcome::Win
+        } else if self.beats(opponent, player) {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// First shape the player can show to get `outcome`, `None` if no
+    /// shape does (e.g. a win against a shape beating all others).
+    pub fn for_outcome(&self, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+        self.shapes()
+            .find(|player| self.outcome(*player, opponent) == outcome)
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Loss => self.loss_score,
+            Outcome::Draw => self.draw_score,
+            Outcome::Win => self.win_score,
+        }
+    }
+
+    /// Points of the player for a single round.
+    pub fn score(&self, player: Shape, opponent: Shape) -> u32 {
+        self.scores[player.0] + self.outcome_score(self.outcome(player, opponent))
+    }
+}
+
+/// Classic rules parsed once, for the puzzle's strategy guide.
+fn classic_rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(Rules::classic)
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::classic()
+    }
+}
 
-    Game { opponent, player }
-}
-
-fn parse_line_v2(line: &str) -> Game {
-    let opponent_char = line.chars().nth(0).expect("Invalid game");
-    let outcome_char = line.chars().nth(2).expect("Invalid game");
-
-    match (opponent_char, outcome_char) {
-        // X -> need to loose
-        ('A', 'X') => Game {
-            opponent: Play::Rock,
-            player: Play::Scissors,
-        },
-        ('B', 'X') => Game {
-            opponent: Play::Paper,
-            player: Play::Rock,
-        },
-        ('C', 'X') => Game {
-            opponent: Play::Scissors,
-            player: Play::Paper,
-        },
-
-        // Y -> need to draw
-        ('A', 'Y') => Game {
-            opponent: Play::Rock,
-            player: Play::Rock,
-        },
-        ('B', 'Y') => Game {
-            opponent: Play::Paper,
-            player: Play::Paper,
-        },
-        ('C', 'Y') => Game {
-            opponent: Play::Scissors,
-            player: Play::Scissors,
-        },
-
-        // X -> need to win
-        ('A', 'Z') => Game {
-            opponent: Play::Rock,
-            player: Play::Paper,
-        },
-        ('B', 'Z') => Game {
-            opponent: Play::Paper,
-            player: Play::Scissors,
-        },
-        ('C', 'Z') => Game {
-            opponent: Play::Scissors,
-            player: Play::Rock,
-        },
-
-        _ => panic!("Invalid char in game"),
-    }
-}
-
-fn points(game: &Game) -> u32 {
-    let shape_points = match game.player {
-        Play::Rock => 1,
-        Play::Paper => 2,
-        Play::Scissors => 3,
+/// A line of a rules table, see [`Rules`] for the format.
+enum RuleLine {
+    Outcomes((u32, u32, u32)),
+    Shape {
+        name: String,
+        score: u32,
+        beats: Vec<String>,
+    },
+}
+
+fn parse_rule_line(line: &str) -> Result<RuleLine> {
+    if line.starts_with("outcomes:") {
+        let re = regex!(r"^outcomes:\s*(?P<loss>\d+)\s+(?P<draw>\d+)\s+(?P<win>\d+)\s*$");
+        let fields = Fields::capture(re, line, "Couldn't parse outcome scores")?;
+        return Ok(RuleLine::Outcomes((
+            fields.get("loss")?,
+            fields.get("draw")?,
+            fields.get("win")?,
+        )));
+    }
+
+    let re =
+        regex!(r"^(?P<name>\w+)\s+(?P<score>\d+)(?:\s+beats\s+(?P<beaten>\w+(?:,\s*\w+)*))?\s*$");
+    let fields = Fields::capture(re, line, "Couldn't parse shape")?;
+    let beats = match fields.text("beaten") {
+        Some(list) => list.split(',').map(|name| name.trim().to_owned()).collect(),
+        None => Vec::new(),
     };
+    Ok(RuleLine::Shape {
+        name: fields.get("name")?,
+        score: fields.get("score")?,
+        beats,
+    })
+}
 
-    let game_points = match game {
-        Game {
-            opponent: Play::Rock,
-            player: Play::Rock,
-        } => 3,
-        Game {
-            opponent: Play::Paper,
-            player: Play::Rock,
-        } => 0,
-        Game {
-            opponent: Play::Scissors,
-            player: Play::Rock,
-        } => 6,
+impl FromStr for Rules {
+    type Err = Error;
 
-        Game {
-            opponent: Play::Rock,
-            player: Play::Paper,
-        } => 6,
-        Game {
-            opponent: Play::Paper,
-            player: Play::Paper,
-        } => 3,
-        Game {
-            opponent: Play::Scissors,
-            player: Play::Paper,
-        } => 0,
+    fn from_str(s: &str) -> Result<Self> {
+        let mut names: Vec<String> = Vec::new();
+        let mut scores = Vec::new();
+        let mut beaten: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut outcomes = None;
 
+        for (idx, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match parse_rule_line(line).map_err(|err| err.shifted(idx))? {
+                RuleLine::Outcomes(outcome_scores) => {
+                    if outcomes.replace(outcome_scores).is_some() {
+                        return Err(Error::parse(idx + 1, 1, "Duplicate outcome scores"));
+                    }
+                }
+                RuleLine::Shape { name, score, beats } => {
+                    if names.contains(&name) {
+                        let reason = format!("Duplicate shape '{}'", name);
+                        return Err(Error::parse(idx + 1, 1, reason));
+                    }
+                    names.push(name);
+                    scores.push(score);
+                    beaten.push((idx + 1, beats));
+                }
+            }
+        }
+
+        let n = names.len();
+        if n == 0 {
+            return Err(Error::parse(1, 1, "No shapes"));
+        }
+
+        let mut beats = vec![vec![false; n]; n];
+        for (shape, (line, list)) in beaten.iter().enumerate() {
+            for other in list {
+                let other = names
+                    .iter()
+                    .position(|name| name == other)
+                    .ok_or_else(|| Error::parse(*line, 1, format!("Unknown shape '{}'", other)))?;
+                if other == shape {
+                    let reason = format!("Shape '{}' can't beat itself", names[shape]);
+                    return Err(Error::parse(*line, 1, reason));
+                }
+                beats[shape][other] = true;
+            }
+        }
+
+        for b in 0..n {
+            for a in 0..b {
+                let reason = match (beats[a][b], beats[b][a]) {
+                    (true, true) => "beat each other",
+                    (false, false) => "don't beat one another",
+                    _ => continue,
+                };
+                let reason = format!("'{}' and '{}' {}", names[a], names[b], reason);
+                return Err(Error::parse(beaten[b].0, 1, reason));
+            }
+        }
+
+        let (loss_score, draw_score, win_score) = outcomes.unwrap_or((0, 3, 6));
+        Ok(Rules {
+            names,
+            scores,
+            beats,
+            loss_score,
+            draw_score,
+            win_score,
+        })
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "outcomes: {} {} {}",
+            self.loss_score, self.draw_score, self.win_score
+        )?;
+        for shape in self.shapes() {
+            write!(f, "{} {}", self.name(shape), self.scores[shape.0])?;
+            let beaten: Vec<&str> = self.beaten_by(shape).map(|s| self.name(s)).collect();
+            if !beaten.is_empty() {
+                write!(f, " beats {}", beaten.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Game {
+    pub opponent: Shape,
+    pub player: Shape,
+}
+
+impl Game {
+    /// Takes [`Play`]s for the classic rules or shapes of any rules.
+    pub fn new(opponent: impl Into<Shape>, player: impl Into<Shape>) -> Self {
         Game {
-            opponent: Play::Rock,
-            player: Play::Scissors,
-        } => 0,
-        Game {
-            opponent: Play::Paper,
-            player: Play::Scissors,
-        } => 6,
-        Game {
-            opponent: Play::Scissors,
-            player: Play::Scissors,
-        } => 3,
-    };
+            opponent: opponent.into(),
+            player: player.into(),
+        }
+    }
+}
+
+/// A line of a strategy guide with the second column not decoded yet.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Round {
+    pub opponent: Shape,
+    pub column: char,
+}
+
+/// Decides what the player shows from the second column of a line of a
+/// strategy guide, `None` for a char it doesn't know. Closures taking
+/// the rules, the opponent's shape and the char decode too.
+pub trait Decoder {
+    fn decode(&self, rules: &Rules, opponent: Shape, column: char) -> Option<Shape>;
+}
+
+impl<F: Fn(&Rules, Shape, char) -> Option<Shape>> Decoder for F {
+    fn decode(&self, rules: &Rules, opponent: Shape, column: char) -> Option<Shape> {
+        self(rules, opponent, column)
+    }
+}
+
+/// X, Y and Z are the shapes to show, in this order.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ShapeMapping(pub [Shape; 3]);
+
+impl ShapeMapping {
+    /// The mapping with the shapes named by `rules`.
+    pub fn describe(&self, rules: &Rules) -> String {
+        let [x, y, z] = self.0.map(|shape| rules.name(shape));
+        format!("X = {}, Y = {}, Z = {}", x, y, z)
+    }
+}
+
+impl From<[Play; 3]> for ShapeMapping {
+    fn from(plays: [Play; 3]) -> Self {
+        ShapeMapping(plays.map(Play::shape))
+    }
+}
+
+/// The first three shapes of the rules, rock, paper and scissors for
+/// the classic ones.
+impl Default for ShapeMapping {
+    fn default() -> Self {
+        ShapeMapping([Shape(0), Shape(1), Shape(2)])
+    }
+}
+
+impl Decoder for ShapeMapping {
+    fn decode(&self, rules: &Rules, _opponent: Shape, column: char) -> Option<Shape> {
+        let shape = self.0["XYZ".find(column)?];
+        (shape.0 < rules.shape_count()).then_some(shape)
+    }
+}
+
+/// X, Y and Z are the outcomes the round needs to end in: a loss, a draw
+/// and a win.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct OutcomeDecoder;
+
+impl Decoder for OutcomeDecoder {
+    fn decode(&self, rules: &Rules, opponent: Shape, column: char) -> Option<Shape> {
+        let outcome = match column {
+            'X' => Outcome::Loss,
+            'Y' => Outcome::Draw,
+            'Z' => Outcome::Win,
+            _ => return None,
+        };
+        rules.for_outcome(opponent, outcome)
+    }
+}
+
+/// Games of a strategy guide, with the second column decoded by
+/// `decoder`.
+pub fn parse_guide(rules: &Rules, input: &str, decoder: &dyn Decoder) -> Result<Vec<Game>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_game(rules, line, decoder).map_err(|err| err.shifted(idx)))
+        .collect()
+}
+
+fn decode_guide(rules: &Rules, rounds: &[Round], decoder: &dyn Decoder) -> Result<Vec<Game>> {
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(idx, round)| decode(rules, round, decoder).map_err(|err| err.shifted(idx)))
+        .collect()
+}
+
+/// Char of a line at the 1-based column
+fn column(line: &str, column: usize) -> Result<char> {
+    line.chars()
+        .nth(column - 1)
+        .ok_or_else(|| Error::parse(1, column, "Invalid game"))
+}
+
+/// The opponent's shapes are A, B, C and so on, in the order of `rules`.
+fn parse_round(rules: &Rules, line: &str) -> Result<Round> {
+    let letter = column(line, 1)?;
+    let opponent = ('A'..='Z')
+        .position(|chr| chr == letter)
+        .map(Shape)
+        .filter(|shape| shape.0 < rules.shape_count())
+        .ok_or_else(|| Error::parse(1, 1, "Invalid char in game"))?;
+
+    Ok(Round {
+        opponent,
+        column: column(line, 3)?,
+    })
+}
+
+fn decode(rules: &Rules, round: &Round, decoder: &dyn Decoder) -> Result<Game> {
+    let player = decoder
+        .decode(rules, round.opponent, round.column)
+        .ok_or_else(|| Error::parse(1, 3, "Invalid char in game"))?;
 
-    shape_points + game_points
+    Ok(Game {
+        opponent: round.opponent,
+        player,
+    })
 }
 
-fn strategy_guide(games: &[Game]) -> u32 {
-    games.iter().map(|g| points(g)).sum()
+fn parse_game(rules: &Rules, line: &str, decoder: &dyn Decoder) -> Result<Game> {
+    decode(rules, &parse_round(rules, line)?, decoder)
+}
+
+fn points(rules: &Rules, game: &Game) -> u32 {
+    rules.score(game.player, game.opponent)
+}
+
+/// Total score of the player over the games, their shapes taken from
+/// `rules`.
+pub fn strategy_guide(rules: &Rules, games: &[Game]) -> u32 {
+    games.iter().map(|g| points(rules, g)).sum()
 }
 
 #[cfg(test)]
@@ -164,91 +568,267 @@ mod tests {
 
     #[test]
     fn test_parse_line() {
-        let result = parse_line("A X");
-        assert_eq!(
-            result,
-            Game {
-                opponent: Play::Rock,
-                player: Play::Rock
-            }
+        let rules = Rules::default();
+        let result = parse_game(&rules, "A X", &ShapeMapping::default()).unwrap();
+        assert_eq!(result, Game::new(Play::Rock, Play::Rock));
+
+        let result = parse_game(&rules, "B X", &ShapeMapping::default()).unwrap();
+        assert_eq!(result, Game::new(Play::Paper, Play::Rock));
+
+        let result = parse_game(&rules, "C Y", &ShapeMapping::default()).unwrap();
+        assert_eq!(result, Game::new(Play::Scissors, Play::Paper));
+
+        let result = parse_game(&rules, "C Z", &ShapeMapping::default()).unwrap();
+        assert_eq!(result, Game::new(Play::Scissors, Play::Scissors));
+    }
+
+    #[test]
+    fn test_parse_line_v2() {
+        let rules = Rules::default();
+        let result = parse_game(&rules, "A X", &OutcomeDecoder).unwrap();
+        assert_eq!(result, Game::new(Play::Rock, Play::Scissors));
+
+        let result = parse_game(&rules, "B X", &OutcomeDecoder).unwrap();
+        assert_eq!(result, Game::new(Play::Paper, Play::Rock));
+
+        let result = parse_game(&rules, "C Y", &OutcomeDecoder).unwrap();
+        assert_eq!(result, Game::new(Play::Scissors, Play::Scissors));
+
+        let result = parse_game(&rules, "C Z", &OutcomeDecoder).unwrap();
+        assert_eq!(result, Game::new(Play::Scissors, Play::Rock));
+    }
+
+    #[test]
+    fn test_points() {
+        let rules = Rules::default();
+        let result = points(
+            &rules,
+            &parse_game(&rules, "A Y", &ShapeMapping::default()).unwrap(),
         );
+        assert_eq!(result, 8);
 
-        let result = parse_line("B X");
-        assert_eq!(
-            result,
-            Game {
-                opponent: Play::Paper,
-                player: Play::Rock
-            }
+        let result = points(
+            &rules,
+            &parse_game(&rules, "B X", &ShapeMapping::default()).unwrap(),
         );
+        assert_eq!(result, 1);
 
-        let result = parse_line("C Y");
-        assert_eq!(
-            result,
-            Game {
-                opponent: Play::Scissors,
-                player: Play::Paper
-            }
+        let result = points(
+            &rules,
+            &parse_game(&rules, "C Z", &ShapeMapping::default()).unwrap(),
         );
+        assert_eq!(result, 6);
+    }
 
-        let result = parse_line("C Z");
-        assert_eq!(
-            result,
-            Game {
-                opponent: Play::Scissors,
-                player: Play::Scissors
+    #[test]
+    fn test_for_outcome() {
+        assert_eq!(Play::for_outcome(Play::Rock, Outcome::Loss), Play::Scissors);
+        assert_eq!(Play::for_outcome(Play::Paper, Outcome::Draw), Play::Paper);
+        assert_eq!(Play::for_outcome(Play::Scissors, Outcome::Win), Play::Rock);
+
+        let rules = Rules::default();
+        for opponent in Play::ALL {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                let player = Play::for_outcome(opponent, outcome);
+                assert_eq!(rules.outcome(player.shape(), opponent.shape()), outcome);
             }
-        );
+        }
+
+        let rules: Rules = "top 1 beats a, b\na 2 beats b\nb 3".parse().unwrap();
+        let top = rules.shape("top").unwrap();
+        assert_eq!(rules.for_outcome(top, Outcome::Win), None);
+        assert_eq!(rules.for_outcome(top, Outcome::Loss), rules.shape("a"));
     }
 
     #[test]
-    fn test_parse_line_v2() {
-        let result = parse_line_v2("A X");
+    fn test_decoders() {
+        let rules = Rules::default();
+        let mapping = ShapeMapping::from([Play::Scissors, Play::Rock, Play::Paper]);
         assert_eq!(
-            result,
-            Game {
-                opponent: Play::Rock,
-                player: Play::Scissors
-            }
+            parse_game(&rules, "A X", &mapping),
+            Ok(Game::new(Play::Rock, Play::Scissors))
         );
-
-        let result = parse_line_v2("B X");
         assert_eq!(
-            result,
-            Game {
-                opponent: Play::Paper,
-                player: Play::Rock
-            }
+            mapping.describe(&rules),
+            "X = scissors, Y = rock, Z = paper"
         );
 
-        let result = parse_line_v2("C Y");
+        // mirrors the opponent for any column
+        let mirror = |_: &Rules, opponent: Shape, _: char| Some(opponent);
         assert_eq!(
-            result,
-            Game {
-                opponent: Play::Scissors,
-                player: Play::Scissors
-            }
+            parse_guide(&rules, "B Q\nC 1", &mirror).unwrap(),
+            vec![
+                Game::new(Play::Paper, Play::Paper),
+                Game::new(Play::Scissors, Play::Scissors)
+            ]
+        );
+        assert_eq!(
+            parse_guide(&rules, "A X\nB W", &OutcomeDecoder),
+            Err(Error::parse(2, 3, "Invalid char in game"))
         );
+    }
 
-        let result = parse_line_v2("C Z");
+    #[test]
+    fn test_best_mapping() {
+        // the puzzle's guide wins every round with another mapping
         assert_eq!(
-            result,
-            Game {
-                opponent: Play::Scissors,
-                player: Play::Rock
+            best_mapping("A Y\nB X\nC Z\n"),
+            Ok((
+                ShapeMapping::from([Play::Scissors, Play::Paper, Play::Rock]),
+                24
+            ))
+        );
+        // every round a win with scissors, rock and paper
+        assert_eq!(
+            best_mapping("A X\nB Y\nC Z\n"),
+            Ok((
+                ShapeMapping::from([Play::Paper, Play::Scissors, Play::Rock]),
+                24
+            ))
+        );
+        assert!(best_mapping("A X\nD Y\n").is_err());
+    }
+
+    #[test]
+    fn test_classic_rules() {
+        let rules = Rules::classic();
+        assert_eq!(rules.shape("paper"), Some(Play::Paper.shape()));
+        assert_eq!(rules.name(Play::Scissors.shape()), "scissors");
+        for play in Play::ALL {
+            assert_eq!(Play::from_shape(play.shape()), Some(play));
+            assert_eq!(play.to_string(), rules.name(play.shape()));
+        }
+        assert_eq!(Play::from_shape(Shape(3)), None);
+        for opponent in rules.shapes() {
+            for player in rules.shapes() {
+                let score = rules.score(player, opponent);
+                let outcome = match (player.0 as i32 - opponent.0 as i32).rem_euclid(3) {
+                    0 => 3,
+                    1 => 6,
+                    _ => 0,
+                };
+                assert_eq!(score, player.0 as u32 + 1 + outcome);
+            }
+        }
+
+        assert_eq!(Rules::cyclic(&["rock", "paper", "scissors"]), Some(rules));
+        assert_eq!(Rules::cyclic(&["a", "b"]), None);
+    }
+
+    #[test]
+    fn test_rpsls_rules() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.shape(name).unwrap();
+        assert!(rules.beats(shape("spock"), shape("scissors")));
+        assert!(rules.beats(shape("lizard"), shape("spock")));
+        assert!(!rules.beats(shape("rock"), shape("spock")));
+        assert_eq!(rules.score(shape("lizard"), shape("paper")), 10);
+        assert_eq!(rules.score(shape("spock"), shape("spock")), 8);
+        assert_eq!(rules.score(shape("rock"), shape("paper")), 1);
+
+        for shape in rules.shapes() {
+            assert_eq!(rules.beaten_by(shape).count(), 2);
+            assert_eq!(rules.beating(shape).count(), 2);
+        }
+
+        // same game in cyclic order, only the scores differ
+        let cyclic = Rules::cyclic(&["rock", "spock", "paper", "lizard", "scissors"]).unwrap();
+        for a in rules.shapes() {
+            for b in rules.shapes() {
+                let (ca, cb) = (
+                    cyclic.shape(rules.name(a)).unwrap(),
+                    cyclic.shape(rules.name(b)).unwrap(),
+                );
+                assert_eq!(rules.beats(a, b), cyclic.beats(ca, cb));
             }
+        }
+    }
+
+    #[test]
+    fn test_rpsls_guide() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.shape(name).unwrap();
+        let guide = parse_guide(&rules, "A Y\nD Z\nE X", &OutcomeDecoder).unwrap();
+        assert_eq!(guide[1], Game::new(shape("lizard"), shape("rock")));
+        assert_eq!(guide[2].player, shape("rock"));
+        assert_eq!(strategy_guide(&rules, &guide), 4 + 7 + 1);
+
+        // only the five shapes have a letter
+        assert_eq!(
+            parse_guide(&rules, "F Y", &OutcomeDecoder),
+            Err(Error::parse(1, 1, "Invalid char in game"))
         );
     }
 
     #[test]
-    fn test_points() {
-        let result = points(&parse_line("A Y"));
-        assert_eq!(result, 8);
+    fn test_rules_roundtrip() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        assert_eq!(rules.to_string().parse(), Ok(rules));
+
+        let rules: Rules = "outcomes: 1 2 10\nup 5 beats down\ndown 7".parse().unwrap();
+        let (up, down) = (rules.shape("up").unwrap(), rules.shape("down").unwrap());
+        assert_eq!(rules.score(up, down), 15);
+        assert_eq!(rules.score(down, up), 8);
+        assert_eq!(rules.score(down, down), 9);
+    }
 
-        let result = points(&parse_line("B X"));
-        assert_eq!(result, 1);
+    #[test]
+    fn test_rules_invalid() {
+        let parse = |rules: &str| rules.parse::<Rules>().unwrap_err();
+        assert_eq!(parse(""), Error::parse(1, 1, "No shapes"));
+        assert_eq!(
+            parse("rock 1\nrock 2"),
+            Error::parse(2, 1, "Duplicate shape 'rock'")
+        );
+        assert_eq!(parse("rock x"), Error::parse(1, 1, "Couldn't parse shape"));
+        assert_eq!(
+            parse("outcomes: 0 3\nrock 1"),
+            Error::parse(1, 1, "Couldn't parse outcome scores")
+        );
+        assert_eq!(
+            parse("rock 1 beats paper\nscissors 3"),
+            Error::parse(1, 1, "Unknown shape 'paper'")
+        );
+        assert_eq!(
+            parse("rock 1 beats rock"),
+            Error::parse(1, 1, "Shape 'rock' can't beat itself")
+        );
+        assert_eq!(
+            parse("rock 1 beats paper\npaper 2 beats rock"),
+            Error::parse(2, 1, "'rock' and 'paper' beat each other")
+        );
+        assert_eq!(
+            parse("rock 1 beats scissors\npaper 2\nscissors 3 beats paper"),
+            Error::parse(2, 1, "'rock' and 'paper' don't beat one another")
+        );
+    }
 
-        let result = points(&parse_line("C Z"));
-        assert_eq!(result, 6);
+    #[test]
+    fn test_parse_line_invalid() {
+        let rules = Rules::default();
+        assert_eq!(
+            parse_game(&rules, "D X", &ShapeMapping::default()),
+            Err(Error::parse(1, 1, "Invalid char in game"))
+        );
+        assert_eq!(
+            parse_game(&rules, "A W", &OutcomeDecoder),
+            Err(Error::parse(1, 3, "Invalid char in game"))
+        );
+        assert_eq!(
+            parse_game(&rules, "A", &ShapeMapping::default()),
+            Err(Error::parse(1, 3, "Invalid game"))
+        );
+    }
+
+    #[test]
+    fn test_parse_guide_line_number() {
+        assert_eq!(
+            parse_guide(
+                &Rules::default(),
+                "A X\nB Y\nC Q\n",
+                &ShapeMapping::default()
+            ),
+            Err(Error::parse(3, 3, "Invalid char in game"))
+        );
     }
 }
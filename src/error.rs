@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Errors surfacing while parsing or solving a puzzle.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    /// Malformed puzzle input, positions are 1-based
+    Parse {
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+    /// Well-formed puzzle input without a solution
+    NoSolution(String),
+    /// Puzzle input couldn't be read
+    Io(String),
+    /// Solver panicked, e.g. bc. it is still `todo!()`
+    Panicked(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    /// Parse error at a byte offset into `input`.
+    pub fn parse_at(input: &str, offset: usize, reason: impl Into<String>) -> Self {
+        let (line, column) = position(input, offset);
+        Error::parse(line, column, reason)
+    }
+
+    pub fn no_solution(reason: impl Into<String>) -> Self {
+        Error::NoSolution(reason.into())
+    }
+
+    /// Moves a parse error found in an excerpt of the input by the
+    /// number of lines preceding that excerpt.
+    pub fn shifted(self, lines: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                reason,
+            } => Error::Parse {
+                line: line + lines,
+                column,
+                reason,
+            },
+            err => err,
+        }
+    }
+
+    /// Moves a parse error found in an excerpt of a line by the number
+    /// of columns preceding that excerpt.
+    pub fn shifted_columns(self, columns: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                reason,
+            } => Error::Parse {
+                line,
+                column: column + columns,
+                reason,
+            },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                reason,
+            } => write!(f, "line {}, column {}: {}", line, column, reason),
+            Error::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            Error::Io(reason) => write!(f, "{}", reason),
+            Error::Panicked(reason) => write!(f, "panicked: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err.to_string())
+    }
+}
+
+/// Line and column (both 1-based) of a byte offset into `input`.
+pub fn position(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Parses every line of the input, errors are reported at the line
+/// they occurred on.
+pub(crate) fn parse_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr<Err = Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.parse().map_err(|err: Error| err.shifted(idx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_offset() {
+        let input = "abc\nde\n\nfgh";
+        assert_eq!(position(input, 0), (1, 1));
+        assert_eq!(position(input, 2), (1, 3));
+        assert_eq!(position(input, 4), (2, 1));
+        assert_eq!(position(input, 8), (4, 1));
+        assert_eq!(position(input, 10), (4, 3));
+    }
+
+    #[test]
+    fn shift_parse_error() {
+        assert_eq!(
+            Error::parse(1, 4, "foo").shifted(9),
+            Error::parse(10, 4, "foo")
+        );
+        assert_eq!(
+            Error::parse(1, 4, "foo").shifted_columns(3),
+            Error::parse(1, 7, "foo")
+        );
+        assert_eq!(
+            Error::no_solution("bar").shifted(9),
+            Error::no_solution("bar")
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::parse(3, 7, "Unknown instruction").to_string(),
+            "line 3, column 7: Unknown instruction"
+        );
+        assert_eq!(
+            Error::no_solution("No marker found").to_string(),
+            "no solution: No marker found"
+        );
+    }
+}
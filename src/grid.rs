@@ -0,0 +1,273 @@
+use ndarray::{Array2, ArrayView1, Axis};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+use crate::point::{Direction, Point2D};
+
+/// Rectangular grid of cells addressed by points, `x` being the column
+/// and `y` the row counted from the top.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+/// Parse error at a cell of a grid.
+pub fn cell_error(pos: Point2D<usize>, reason: impl Into<String>) -> Error {
+    Error::parse(pos.y + 1, pos.x + 1, reason)
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per char, `cell` may also take note of markers
+    /// and rejects chars with e.g. [`cell_error`].
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(Point2D<usize>, char) -> Result<T>,
+    ) -> Result<Self> {
+        let cols = input
+            .lines()
+            .next()
+            .map(|line| line.chars().count())
+            .filter(|cols| *cols > 0)
+            .ok_or_else(|| Error::parse(1, 1, "No input"))?;
+
+        let mut cells = Vec::new();
+        let mut rows = 0;
+        for (row, line) in input.lines().enumerate() {
+            let mut len = 0;
+            for (col, chr) in line.chars().enumerate() {
+                if col == cols {
+                    return Err(Error::parse(row + 1, col + 1, "Rows of different length"));
+                }
+                cells.push(cell(Point2D::new(col, row), chr)?);
+                len += 1;
+            }
+            if len < cols {
+                return Err(Error::parse(row + 1, len + 1, "Rows of different length"));
+            }
+            rows += 1;
+        }
+
+        let cells = Array2::from_shape_vec((rows, cols), cells)
+            .map_err(|_| Error::parse(rows + 1, 1, "Invalid shape"))?;
+        Ok(Grid { cells })
+    }
+
+    pub fn from_array(cells: Array2<T>) -> Self {
+        Grid { cells }
+    }
+
+    pub fn array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn into_array(self) -> Array2<T> {
+        self.cells
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn contains(&self, pos: Point2D<usize>) -> bool {
+        pos.x < self.cols() && pos.y < self.rows()
+    }
+
+    pub fn get(&self, pos: Point2D<usize>) -> Option<&T> {
+        self.cells.get(pos.index())
+    }
+
+    pub fn get_mut(&mut self, pos: Point2D<usize>) -> Option<&mut T> {
+        self.cells.get_mut(pos.index())
+    }
+
+    /// Cells in row-major order along with their position.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D<usize>, &T)> {
+        self.cells
+            .indexed_iter()
+            .map(|((row, col), cell)| (Point2D::new(col, row), cell))
+    }
+
+    /// Neighbor of `pos` in `direction`, if it is inside the grid.
+    pub fn step(&self, pos: Point2D<usize>, direction: Direction) -> Option<Point2D<usize>> {
+        pos.checked_offset(direction.grid_unit::<i32>())
+            .filter(|next| self.contains(*next))
+    }
+
+    /// Orthogonal neighbors of `pos` inside the grid.
+    pub fn neighbors(&self, pos: Point2D<usize>) -> impl Iterator<Item = Point2D<usize>> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    /// Orthogonal and diagonal neighbors of `pos` inside the grid.
+    pub fn moore_neighbors(
+        &self,
+        pos: Point2D<usize>,
+    ) -> impl Iterator<Item = Point2D<usize>> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    /// Cells from `pos`, excluding it, in `direction` up to the edge.
+    pub fn ray(
+        &self,
+        pos: Point2D<usize>,
+        direction: Direction,
+    ) -> impl Iterator<Item = (Point2D<usize>, &T)> {
+        std::iter::successors(self.step(pos, direction), move |pos| {
+            self.step(*pos, direction)
+        })
+        .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, row: usize) -> ArrayView1<'_, T> {
+        self.cells.index_axis(Axis(0), row)
+    }
+
+    pub fn column(&self, col: usize) -> ArrayView1<'_, T> {
+        self.cells.index_axis(Axis(1), col)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.map(f),
+        }
+    }
+
+    /// Draws the grid with one char per cell.
+    pub fn render(&self, mut glyph: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.cols() + 1) * self.rows());
+        for row in self.cells.rows() {
+            text.extend(row.iter().map(&mut glyph));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Point2D<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point2D<usize>) -> &T {
+        &self.cells[pos.index()]
+    }
+}
+
+impl<T> IndexMut<Point2D<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point2D<usize>) -> &mut T {
+        &mut self.cells[pos.index()]
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|chr| *chr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const MAP: &str = indoc! {"
+        ab#
+        .P.
+        c.d
+        e#f
+    "};
+
+    fn chars(input: &str) -> Result<Grid<char>> {
+        Grid::parse(input, |_, chr| Ok(chr))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = chars(MAP).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (4, 3));
+        assert_eq!(grid[Point2D::new(2, 0)], '#');
+        assert_eq!(grid[Point2D::new(0, 3)], 'e');
+        assert_eq!(grid.get(Point2D::new(3, 0)), None);
+        assert_eq!(grid.to_string(), MAP);
+    }
+
+    #[test]
+    fn parse_with_marker() {
+        let mut marker = None;
+        let grid = Grid::parse(MAP, |pos, chr| match chr {
+            'P' => {
+                marker = Some(pos);
+                Ok(false)
+            }
+            '#' => Ok(true),
+            _ if chr.is_ascii_lowercase() || chr == '.' => Ok(false),
+            _ => Err(cell_error(pos, "Unsupported symbol")),
+        })
+        .unwrap();
+        assert_eq!(marker, Some(Point2D::new(1, 1)));
+        assert_eq!(grid.iter().filter(|(_, wall)| **wall).count(), 2);
+        let walls = grid.render(|wall| if *wall { '#' } else { '.' });
+        assert_eq!(walls, "..#\n...\n...\n.#.\n");
+
+        let grid: Result<Grid<u32>> = Grid::parse("12\n3x\n", |pos, chr| {
+            chr.to_digit(10)
+                .ok_or_else(|| cell_error(pos, "Not a digit"))
+        });
+        assert_eq!(grid, Err(Error::parse(2, 2, "Not a digit")));
+    }
+
+    #[test]
+    fn parse_ragged() {
+        assert_eq!(chars(""), Err(Error::parse(1, 1, "No input")));
+        assert_eq!(
+            chars("abc\nab\nabc\n"),
+            Err(Error::parse(2, 3, "Rows of different length"))
+        );
+        assert_eq!(
+            chars("abc\nabcd\n"),
+            Err(Error::parse(2, 4, "Rows of different length"))
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = chars(MAP).unwrap();
+        let corner: Vec<_> = grid.neighbors(Point2D::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point2D::new(1, 0), Point2D::new(0, 1)]);
+        assert_eq!(grid.neighbors(Point2D::new(1, 1)).count(), 4);
+        assert_eq!(grid.moore_neighbors(Point2D::new(1, 1)).count(), 8);
+        assert_eq!(grid.moore_neighbors(Point2D::new(2, 3)).count(), 3);
+        assert_eq!(
+            grid.step(Point2D::new(1, 1), Direction::North),
+            Some(Point2D::new(1, 0))
+        );
+        assert_eq!(grid.step(Point2D::new(1, 0), Direction::North), None);
+    }
+
+    #[test]
+    fn views() {
+        let grid = chars(MAP).unwrap();
+        assert_eq!(grid.row(2).to_vec(), vec!['c', '.', 'd']);
+        assert_eq!(grid.column(1).to_vec(), vec!['b', 'P', '.', '#']);
+
+        let ray: String = grid
+            .ray(Point2D::new(1, 3), Direction::North)
+            .map(|(_, chr)| *chr)
+            .collect();
+        assert_eq!(ray, ".Pb");
+
+        let ray: Vec<_> = grid.ray(Point2D::new(0, 0), Direction::SouthEast).collect();
+        assert_eq!(
+            ray,
+            vec![(Point2D::new(1, 1), &'P'), (Point2D::new(2, 2), &'d')]
+        );
+        assert_eq!(grid.ray(Point2D::new(2, 0), Direction::East).count(), 0);
+    }
+}
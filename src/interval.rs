@@ -0,0 +1,351 @@
+use num_traits::int::PrimInt;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Inclusive range of integers, empty when `start > end` just like
+/// `start..=end`.
+#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Number of values, saturating for an interval spanning all of `T`.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end
+                .checked_sub(&self.start)
+                .map_or(T::max_value(), |span| span.saturating_add(T::one()))
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether all values of `other` are in this interval, which holds
+    /// for any empty `other`.
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether both intervals share a value.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Values in both intervals, `None` if there are none.
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let intersection = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Values in either interval, `None` if they neither overlap nor
+    /// touch and so have no single interval as union.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first.end.saturating_add(T::one()) < second.start {
+            return None;
+        }
+        Some(Interval::new(first.start, first.end.max(second.end)))
+    }
+}
+
+/// Parses `a-b`, a leading `-` is taken as the sign of `a`.
+impl<T: PrimInt + FromStr> FromStr for Interval<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let separator = s
+            .char_indices()
+            .skip(1)
+            .find(|(_, chr)| *chr == '-')
+            .map(|(idx, _)| idx)
+            .ok_or_else(|| Error::parse(1, 1, "Missing '-' in range"))?;
+        let (start, end) = (&s[..separator], &s[separator + 1..]);
+
+        let start = start
+            .parse()
+            .map_err(|_| Error::parse(1, 1, "Couldn't parse range start"))?;
+        let end = end
+            .parse()
+            .map_err(|_| Error::parse(1, separator + 2, "Couldn't parse range end"))?;
+        Ok(Interval::new(start, end))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Union of intervals, kept as sorted, disjoint and non-touching
+/// intervals.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set, saturating like [`Interval::len`].
+    pub fn len(&self) -> T {
+        self.intervals.iter().fold(T::zero(), |len, interval| {
+            len.saturating_add(interval.len())
+        })
+    }
+
+    /// The disjoint intervals in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Adds all values of `interval`, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // first interval that may be merged, i.e. doesn't end before
+        let first = self
+            .intervals
+            .partition_point(|other| other.end.saturating_add(T::one()) < interval.start);
+        let mut merged = interval;
+        let mut last = first;
+        while let Some(union) = self
+            .intervals
+            .get(last)
+            .and_then(|other| merged.union(other))
+        {
+            merged = union;
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Removes all values of `interval`, splitting intervals it cuts.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = Vec::with_capacity(2);
+        let (head, tail) = (self.intervals[first], self.intervals[last - 1]);
+        if head.start < interval.start {
+            remaining.push(Interval::new(head.start, interval.start - T::one()));
+        }
+        if interval.end < tail.end {
+            remaining.push(Interval::new(interval.end + T::one(), tail.end));
+        }
+        self.intervals.splice(first..last, remaining);
+    }
+
+    /// Values in this set but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut difference = self.clone();
+        for interval in &other.intervals {
+            difference.remove(*interval);
+        }
+        difference
+    }
+}
+
+impl<T: PrimInt> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i64, end: i64) -> Interval<i64> {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("2-4".parse(), Ok(Interval::new(2u64, 4)));
+        assert_eq!("-5--3".parse(), Ok(iv(-5, -3)));
+        assert_eq!(
+            "10".parse::<Interval<u64>>(),
+            Err(Error::parse(1, 1, "Missing '-' in range"))
+        );
+        assert_eq!(
+            "x-4".parse::<Interval<u64>>(),
+            Err(Error::parse(1, 1, "Couldn't parse range start"))
+        );
+        assert_eq!(
+            "12-y".parse::<Interval<u64>>(),
+            Err(Error::parse(1, 4, "Couldn't parse range end"))
+        );
+        assert_eq!(iv(-5, 3).to_string(), "-5-3");
+    }
+
+    #[test]
+    fn contains_and_overlaps() {
+        assert!(iv(2, 8).contains(2));
+        assert!(iv(2, 8).contains(8));
+        assert!(!iv(2, 8).contains(9));
+        assert!(!iv(3, 2).contains(2));
+
+        assert!(iv(2, 8).contains_interval(&iv(3, 7)));
+        assert!(!iv(3, 7).contains_interval(&iv(2, 8)));
+        assert!(iv(3, 7).contains_interval(&iv(12, 6)));
+
+        assert!(iv(5, 7).overlaps(&iv(7, 9)));
+        assert!(!iv(2, 3).overlaps(&iv(4, 5)));
+        assert!(!iv(2, 8).overlaps(&iv(5, 4)));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        assert_eq!(iv(2, 6).intersection(&iv(4, 8)), Some(iv(4, 6)));
+        assert_eq!(iv(2, 3).intersection(&iv(4, 5)), None);
+
+        assert_eq!(iv(2, 6).union(&iv(4, 8)), Some(iv(2, 8)));
+        assert_eq!(iv(4, 5).union(&iv(2, 3)), Some(iv(2, 5)));
+        assert_eq!(iv(2, 3).union(&iv(5, 6)), None);
+        assert_eq!(iv(2, 3).union(&iv(1, 0)), Some(iv(2, 3)));
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(iv(2, 4).len(), 3);
+        assert_eq!(iv(4, 2).len(), 0);
+        assert_eq!(Interval::new(0u64, u64::MAX).len(), u64::MAX);
+        assert_eq!(Interval::new(u64::MAX - 1, u64::MAX).len(), 2);
+
+        assert_eq!(iv(-3, 4).len(), 8);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), i64::MAX);
+        assert_eq!(Interval::new(-100i8, 100).len(), i8::MAX);
+        assert_eq!(Interval::new(i8::MIN, -2).len(), i8::MAX);
+        assert_eq!(Interval::new(i8::MIN, -1).len(), i8::MAX);
+
+        let set: IntervalSet<i64> = [iv(i64::MIN, -1), iv(1, i64::MAX)].into_iter().collect();
+        assert_eq!(set.len(), i64::MAX);
+    }
+
+    #[test]
+    fn set_insert() {
+        let mut set: IntervalSet<i64> = [iv(10, 12), iv(1, 3), iv(20, 25)].into_iter().collect();
+        assert_eq!(set.intervals(), &[iv(1, 3), iv(10, 12), iv(20, 25)]);
+
+        set.insert(iv(4, 9));
+        assert_eq!(set.intervals(), &[iv(1, 12), iv(20, 25)]);
+
+        set.insert(iv(14, 30));
+        assert_eq!(set.intervals(), &[iv(1, 12), iv(14, 30)]);
+
+        set.insert(iv(0, 40));
+        assert_eq!(set.intervals(), &[iv(0, 40)]);
+        assert_eq!(set.len(), 41);
+
+        set.insert(iv(50, 49));
+        assert_eq!(set.intervals(), &[iv(0, 40)]);
+    }
+
+    #[test]
+    fn set_remove() {
+        let mut set: IntervalSet<i64> = [iv(0, 10), iv(20, 30)].into_iter().collect();
+
+        set.remove(iv(3, 5));
+        assert_eq!(set.intervals(), &[iv(0, 2), iv(6, 10), iv(20, 30)]);
+        assert!(set.contains(6));
+        assert!(!set.contains(4));
+
+        set.remove(iv(8, 25));
+        assert_eq!(set.intervals(), &[iv(0, 2), iv(6, 7), iv(26, 30)]);
+
+        set.remove(iv(11, 19));
+        assert_eq!(set.len(), 10);
+
+        let other: IntervalSet<i64> = [iv(-5, 0), iv(30, 35)].into_iter().collect();
+        let difference = set.difference(&other);
+        assert_eq!(difference.intervals(), &[iv(1, 2), iv(6, 7), iv(26, 29)]);
+
+        set.remove(iv(i64::MIN, i64::MAX));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_full_range() {
+        let mut set: IntervalSet<u8> = [Interval::new(0, 255)].into_iter().collect();
+        set.remove(Interval::new(0, 0));
+        set.remove(Interval::new(255, 255));
+        assert_eq!(set.intervals(), &[Interval::new(1, 254)]);
+        set.insert(Interval::new(250, 255));
+        assert_eq!(set.intervals(), &[Interval::new(1, 255)]);
+    }
+}
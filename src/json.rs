@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+
+/// Minimal JSON value, enough to report answers to other tools.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys keep their insertion order
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+}
+
+impl From<&str> for Json {
+    fn from(text: &str) -> Self {
+        Json::String(text.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(text: String) -> Self {
+        Json::String(text)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+/// Numbers stay numbers, images keep their line breaks.
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Number(num) => Json::Number(*num),
+            Answer::Text(_) | Answer::Image(_) => Json::String(answer.to_string()),
+        }
+    }
+}
+
+/// Fields `answer` and `error`, exactly one of them is `null`.
+pub fn answer_fields(answer: &Result<Answer>) -> [(&'static str, Json); 2] {
+    let error: Option<&Error> = answer.as_ref().err();
+    [
+        ("answer", answer.as_ref().ok().into()),
+        ("error", error.map(Error::to_string).into()),
+    ]
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for chr in text.chars() {
+        match chr {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(num) => write!(f, "{}", num),
+            Json::String(text) => write_string(f, text),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let json = Json::object([
+            ("day", Json::Number(10)),
+            ("solved", Json::Bool(false)),
+            ("answer", Json::Null),
+            (
+                "parts",
+                Json::Array(vec![Json::from("a"), Json::Number(-1)]),
+            ),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            r#"{"day":10,"solved":false,"answer":null,"parts":["a",-1]}"#
+        );
+    }
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(
+            Json::from("say \"hi\"\\\n\t\u{1}").to_string(),
+            r#""say \"hi\"\\\n\t\u0001""#
+        );
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Json::from(&Answer::Number(24)), Json::Number(24));
+        assert_eq!(
+            Json::from(&Answer::Image("#.\n.#\n".to_owned())).to_string(),
+            r##""#.\n.#""##
+        );
+
+        let [answer, error] = answer_fields(&Err(Error::no_solution("No marker")));
+        assert_eq!(answer, ("answer", Json::Null));
+        assert_eq!(error, ("error", Json::from("no solution: No marker")));
+    }
+}
@@ -1,40 +1,390 @@
+use aoc2022::answer::Answer;
+use aoc2022::bench::{format_duration, Timings};
+use aoc2022::day_one;
+use aoc2022::error::{Error, Result};
+use aoc2022::json::{self, Json};
+use aoc2022::report::Table;
+use aoc2022::scaffold;
+use aoc2022::solution::{self, Day, Run, DAYS};
+use aoc2022::verify::{verify, Expected, Verdict};
 use std::env;
 use std::fs;
+use std::io::{self, BufReader, ErrorKind};
+use std::panic;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc2022 [--json] <day> [file | -]
+       aoc2022 [--json] all
+       aoc2022 check [day]
+       aoc2022 bench [day] [--runs <n>]
+       aoc2022 1 --stats [file | -]
+       aoc2022 new <day>";
+
+const DEFAULT_RUNS: usize = 10;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--json")
+        .collect();
+
+    match args.as_slice() {
+        ["all"] => all_days(json),
+        ["check"] => check(DAYS.iter().collect()),
+        ["check", day] => match day.parse().ok().and_then(solution::day) {
+            Some(day) => check(vec![day]),
+            None => unknown_day(),
+        },
+        ["bench", rest @ ..] => match bench_args(rest) {
+            Some((day, runs)) => bench(day, runs),
+            None => usage(),
+        },
+        ["new", day] => match day.parse() {
+            Ok(day) => new_day(day),
+            Err(_) => usage(),
+        },
+        ["1", "--stats"] => calorie_stats(&input_path(1)),
+        ["1", "--stats", file_path] => calorie_stats(file_path),
+        [day] => {
+            let day: u32 = day.parse().unwrap_or_else(|_| usage());
+            single_day(day, &input_path(day), json);
+        }
+        [day, file_path] => {
+            let day: u32 = day.parse().unwrap_or_else(|_| usage());
+            single_day(day, file_path, json);
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn unknown_day() -> ! {
+    eprintln!("Unknown day");
+    process::exit(2);
+}
+
+fn input_path(day: u32) -> String {
+    format!("./input/day{}.txt", day)
+}
+
+fn answers_path(day: u32) -> String {
+    format!("./input/day{}.answers", day)
+}
+
+/// Solves a day with the input read from `file_path`, or from stdin
+/// for `-`.
+fn single_day(day: u32, file_path: &str, json: bool) {
+    let day = match solution::day(day) {
+        Some(day) => day,
+        None => unknown_day(),
+    };
+
+    let run = match file_path {
+        "-" => day.run_from(io::stdin().lock()),
+        file_path => match fs::File::open(file_path) {
+            Ok(file) => day.run_from(file),
+            Err(err) if json => Run::failed(err.into(), Duration::ZERO),
+            Err(err) => {
+                eprintln!("{}: {}", file_path, err);
+                process::exit(1);
+            }
+        },
+    };
+
+    let solved = if json {
+        println!("{}", Json::Array(json_records(day.number, &run)));
+        run.part_one.answer.is_ok() && run.part_two.answer.is_ok()
+    } else {
+        let (part_one, part_two) = run.into_parts();
+        let solved_one = print_answer("Part one", &part_one);
+        let solved_two = print_answer("Part two", &part_two);
+        solved_one && solved_two
+    };
+
+    if !solved {
+        process::exit(1);
+    }
+}
+
+/// One record per part with its answer or error and the time it took.
+fn json_records(day: u32, run: &Run) -> Vec<Json> {
+    [(1, &run.part_one), (2, &run.part_two)]
+        .into_iter()
+        .map(|(part, timed)| {
+            let mut fields = vec![
+                ("day", Json::Number(day.into())),
+                ("part", Json::Number(part)),
+            ];
+            fields.extend(json::answer_fields(&timed.answer));
+            fields.push(("elapsed_ns", Json::Number(timed.elapsed.as_nanos() as i128)));
+            fields.push((
+                "parse_elapsed_ns",
+                Json::Number(run.parse_elapsed.as_nanos() as i128),
+            ));
+            Json::object(fields)
+        })
+        .collect()
+}
+
+/// Prints the answer or the error of a part, returns whether it was solved.
+fn print_answer(label: &str, answer: &Result<Answer>) -> bool {
+    match answer {
+        Ok(answer) if answer.is_multiline() => println!("{}:\n{}", label, answer),
+        Ok(answer) => println!("{}: {}", label, answer),
+        Err(err) => eprintln!("{}: error: {}", label, err),
+    }
+    answer.is_ok()
+}
+
+/// Solves every day with its default input, failing days are reported
+/// in the table instead of stopping the run.
+fn all_days(json: bool) {
+    // panics are reported as errors, keep them from cluttering the table
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut table = Table::new(&["Day", "Part one", "Part two"]);
+    let mut records = Vec::new();
+    let mut failed = false;
+
+    for day in DAYS {
+        let run = match fs::File::open(input_path(day.number)) {
+            Ok(file) => day.run_from(file),
+            Err(err) => Run::failed(err.into(), Duration::ZERO),
+        };
+
+        if json {
+            records.extend(json_records(day.number, &run));
+        }
+
+        let (part_one, part_two) = run.into_parts();
+        failed |= part_one.is_err() || part_two.is_err();
+        table.row(vec![
+            day.number.to_string(),
+            cell(&part_one),
+            cell(&part_two),
+        ]);
+    }
+
+    if json {
+        println!("{}", Json::Array(records));
+    } else {
+        print!("{}", table);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn cell(answer: &Result<Answer>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+/// Prints statistics over the calories the elves of day one carry,
+/// including lines that had to be skipped.
+fn calorie_stats(file_path: &str) {
+    let report = match file_path {
+        "-" => day_one::report(io::stdin().lock()),
+        file_path => fs::File::open(file_path)
+            .map_err(Error::from)
+            .and_then(|file| day_one::report(BufReader::new(file))),
+    };
 
-    match args.len() {
-        2 => {
-            let day: u32 = args[1].parse().expect("Not a valid day");
-            let file_path = format!("./input/day{}.txt", day);
-            single_day(day, file_path.as_ref());
+    match report {
+        Ok(report) => print!("{}", report),
+        Err(err) => {
+            eprintln!("{}: {}", file_path, err);
+            process::exit(1);
         }
-        3 => {
-            let day: u32 = args[1].parse().expect("Not a valid day");
-            let file_path = &args[2];
-            single_day(day, file_path);
+    }
+}
+
+/// Parses the optional day and `--runs <n>` of the bench mode.
+fn bench_args(args: &[&str]) -> Option<(Option<u32>, usize)> {
+    let mut day = None;
+    let mut runs = DEFAULT_RUNS;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--runs" => runs = args.next()?.parse().ok().filter(|runs| *runs > 0)?,
+            arg if day.is_none() => day = Some(arg.parse().ok()?),
+            _ => return None,
+        }
+    }
+
+    Some((day, runs))
+}
+
+/// Times parsing and both parts of one or all days with their default
+/// input.
+fn bench(day: Option<u32>, runs: usize) {
+    panic::set_hook(Box::new(|_| {}));
+
+    let days: Vec<&Day> = match day {
+        Some(number) => match solution::day(number) {
+            Some(day) => vec![day],
+            None => unknown_day(),
+        },
+        None => DAYS.iter().collect(),
+    };
+
+    let mut table = Table::new(&["Day", "Stage", "Min", "Median", "Max"]);
+    let mut failed = false;
+
+    for day in days {
+        let bench = fs::read_to_string(input_path(day.number))
+            .map_err(Error::from)
+            .and_then(|input| day.bench(&input, runs));
+
+        let stages = match bench {
+            Ok(bench) => vec![
+                ("parse", Ok(bench.parse)),
+                ("part one", bench.part_one),
+                ("part two", bench.part_two),
+            ],
+            Err(err) => vec![("parse", Err(err))],
+        };
+
+        for (stage, timings) in stages {
+            failed |= timings.is_err();
+            table.row(timing_row(day.number, stage, &timings));
+        }
+    }
+
+    println!("{} run(s) per day", runs);
+    print!("{}", table);
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn timing_row(day: u32, stage: &str, timings: &Result<Timings>) -> Vec<String> {
+    let mut row = vec![day.to_string(), stage.to_owned()];
+    match timings {
+        Ok(timings) => row.extend(
+            [timings.min(), timings.median(), timings.max()]
+                .into_iter()
+                .map(format_duration),
+        ),
+        Err(err) => row.push(format!("error: {}", err)),
+    }
+    row
+}
+
+/// Solves the days with their default input and compares the answers
+/// to the ones in their answers file.
+fn check(days: Vec<&Day>) {
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut table = Table::new(&["Day", "Part one", "Part two"]);
+    let mut failed = false;
+
+    for day in days {
+        let (part_one, part_two) = match read_expected(day.number) {
+            Ok(expected) => {
+                let (part_one, part_two) = match fs::read_to_string(input_path(day.number)) {
+                    Ok(input) => day.parts(&input),
+                    Err(err) => {
+                        let err = Error::from(err);
+                        (Err(err.clone()), Err(err))
+                    }
+                };
+                (
+                    verify(&part_one, expected.part_one.as_deref()),
+                    verify(&part_two, expected.part_two.as_deref()),
+                )
+            }
+            Err(err) => (Verdict::Error(err.clone()), Verdict::Error(err)),
+        };
+
+        failed |= part_one.is_failure() || part_two.is_failure();
+        table.row(vec![
+            day.number.to_string(),
+            part_one.to_string(),
+            part_two.to_string(),
+        ]);
+    }
+
+    print!("{}", table);
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Expected answers of a day, a missing answers file leaves both parts
+/// unchecked.
+fn read_expected(day: u32) -> Result<Expected> {
+    let path = answers_path(day);
+    match fs::read_to_string(&path) {
+        Ok(answers) => answers.parse().map_err(|err| match err {
+            Error::Parse {
+                line,
+                column,
+                reason,
+            } => Error::parse(line, column, format!("{} in {}", reason, path)),
+            err => err,
+        }),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Expected::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Creates the module and integration test of a new day and registers
+/// it, run from the root of the crate.
+fn new_day(day: u32) {
+    let (Some(module_name), Some(module), Some(tests)) = (
+        scaffold::module_name(day),
+        scaffold::module_source(day),
+        scaffold::test_source(day),
+    ) else {
+        eprintln!("Day {} is not part of the advent", day);
+        process::exit(2);
+    };
+
+    let module_path = format!("src/{}.rs", module_name);
+    let tests_path = format!("tests/{}.rs", module_name);
+    if solution::day(day).is_some() || Path::new(&module_path).exists() {
+        eprintln!("Day {} already exists", day);
+        process::exit(1);
+    }
+
+    let result = (|| -> io::Result<()> {
+        let lib_rs = fs::read_to_string("src/lib.rs")?;
+        let solution_rs = fs::read_to_string("src/solution.rs")?;
+        let (Some(lib_rs), Some(solution_rs)) = (
+            scaffold::register_module(&lib_rs, day),
+            scaffold::register_day(&solution_rs, day),
+        ) else {
+            return Err(io::Error::other("Day is already registered"));
+        };
+
+        fs::write(&module_path, module)?;
+        fs::write(&tests_path, tests)?;
+        fs::write("src/lib.rs", lib_rs)?;
+        fs::write("src/solution.rs", solution_rs)
+    })();
+
+    match result {
+        Ok(()) => println!("Created {} and {}", module_path, tests_path),
+        Err(err) => {
+            eprintln!("Couldn't create day {}: {}", day, err);
+            process::exit(1);
         }
-        _ => panic!("Unsupported number of arguments"),
-    }
-}
-
-fn single_day(day: u32, file_path: &str) {
-    let input = fs::read_to_string(file_path).expect("Unable to read file");
-
-    match day {
-        1 => aoc2022::day_one::both(&input),
-        2 => aoc2022::day_two::both(&input),
-        3 => aoc2022::day_three::both(&input),
-        4 => aoc2022::day_four::both(&input),
-        5 => aoc2022::day_five::both(&input),
-        6 => aoc2022::day_six::both(&input),
-        7 => aoc2022::day_seven::both(&input),
-        8 => aoc2022::day_eight::both(&input),
-        9 => aoc2022::day_nine::both(&input),
-        10 => aoc2022::day_ten::both(&input),
-        11 => aoc2022::day_eleven::both(&input),
-        12 => aoc2022::day_twelve::both(&input),
-        _ => println!("Unknown day"),
     }
 }
@@ -0,0 +1,267 @@
+//! Number theory on primitive integers: gcd, lcm, modular inverse and
+//! the chinese remainder theorem. Everything is generic over
+//! [`PrimInt`], so it works the same for `u64`, `u128` and `i128`.
+//! Signed arguments are taken by absolute value where a sign makes no
+//! sense (e.g. gcd, moduli must be positive).
+
+use num_traits::int::PrimInt;
+use num_traits::Signed;
+
+fn abs<T: PrimInt>(value: T) -> T {
+    if value < T::zero() {
+        T::zero() - value
+    } else {
+        value
+    }
+}
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, never negative. `None` if it doesn't fit in
+/// `T`.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    (abs(a) / gcd(a, b)).checked_mul(&abs(b))
+}
+
+/// Greatest common divisor of all values, 0 for none.
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// Least common multiple of all values, 1 for none. `None` if it
+/// doesn't fit in `T`.
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, value| lcm(acc, value))
+}
+
+/// Extended euclidean algorithm, returns `(g, x, y)` with
+/// `a * x + b * y == g` where `g` is the gcd of `a` and `b`.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `value` reduced to `0..modulus`, also for negative values.
+pub fn rem_euclid<T: PrimInt>(value: T, modulus: T) -> T {
+    let modulus = abs(modulus);
+    let rem = value % modulus;
+    if rem < T::zero() {
+        rem + modulus
+    } else {
+        rem
+    }
+}
+
+/// `(a + b) % modulus` for `a` and `b` in `0..modulus`, without
+/// overflowing.
+fn add_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) % modulus` for `a` and `b` in `0..modulus`, without
+/// overflowing even when the product doesn't fit in `T`.
+pub fn mul_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+
+    // double and add over the bits of b
+    let (mut a, mut b) = (a, b);
+    let mut product = T::zero();
+    while b != T::zero() {
+        if b & T::one() == T::one() {
+            product = add_mod(product, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b >> 1;
+    }
+    product
+}
+
+/// Modular inverse of `a`, i.e. `x` in `0..modulus` with
+/// `a * x ≡ 1 (mod modulus)`. `None` if `a` and `modulus` aren't coprime
+/// or `modulus` is 0.
+pub fn mod_inverse<T: PrimInt>(a: T, modulus: T) -> Option<T> {
+    let modulus = abs(modulus);
+    if modulus == T::zero() {
+        return None;
+    }
+
+    // extended euclid on magnitudes only, so unsigned types work too:
+    // the coefficients alternate in sign and never exceed the modulus
+    let (mut old_r, mut r) = (rem_euclid(a, modulus), modulus);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let mut old_negative = false;
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x + q * x);
+        old_negative = !old_negative;
+    }
+
+    if old_r != T::one() {
+        return (modulus == T::one()).then(T::zero);
+    }
+
+    let x = rem_euclid(old_x, modulus);
+    Some(if old_negative && x != T::zero() {
+        modulus - x
+    } else {
+        x
+    })
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for all given
+/// `(residue, modulus)` pairs. The moduli don't need to be coprime.
+///
+/// Returns the smallest non-negative solution and the modulus it's
+/// unique for, i.e. the lcm of all moduli. `None` if the congruences
+/// contradict each other, a modulus is 0 or the lcm doesn't fit in `T`.
+pub fn crt<T: PrimInt>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), |(x, m), (residue, modulus)| {
+            let modulus = abs(modulus);
+            if modulus == T::zero() {
+                return None;
+            }
+            let residue = rem_euclid(residue, modulus);
+
+            // x + m * k ≡ residue (mod modulus) is solvable iff the gcd
+            // divides the difference
+            let g = gcd(m, modulus);
+            let diff = add_mod(residue, modulus - rem_euclid(x, modulus), modulus);
+            if diff % g != T::zero() {
+                return None;
+            }
+
+            let reduced = modulus / g;
+            let combined = (m / g).checked_mul(&modulus)?;
+            let k = mul_mod(
+                diff / g,
+                mod_inverse(rem_euclid(m / g, reduced), reduced)?,
+                reduced,
+            );
+            // m * k < m * reduced == combined
+            Some((add_mod(x, m * k, combined), combined))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(gcd(0u64, 0), 0);
+        assert_eq!(gcd(-12i128, 18), 6);
+
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+        assert_eq!(lcm(-4i128, 6), Some(12));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(gcd_all(Vec::<u64>::new()), 0);
+        assert_eq!(lcm_all([23u64, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm_all([2u64, 4, 8]), Some(8));
+        assert_eq!(lcm_all(Vec::<u128>::new()), Some(1));
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240i128, 46), (46, 240), (-7, 3), (0, 5), (17, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(mod_inverse(3u64, 11), Some(4));
+        assert_eq!(mod_inverse(10u64, 17), Some(12));
+        assert_eq!(mod_inverse(-3i128, 11), Some(7));
+        assert_eq!(mod_inverse(6u64, 9), None);
+        assert_eq!(mod_inverse(5u64, 0), None);
+        assert_eq!(mod_inverse(5u64, 1), Some(0));
+
+        for modulus in [2u64, 7, 13, 97] {
+            for a in 1..modulus {
+                assert_eq!(mod_inverse(a, modulus).map(|x| a * x % modulus), Some(1));
+            }
+        }
+
+        let prime = (1u128 << 127) - 1;
+        let a = prime - 2;
+        let x = mod_inverse(a, prime).unwrap();
+        assert_eq!(mul_mod(a, x, prime), 1);
+    }
+
+    #[test]
+    fn mul_mod_overflow() {
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+        assert_eq!(mul_mod(7u128, 8, 10), 6);
+        let m = u128::MAX - 4;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0i128, 3), (3, 4), (4, 5)]), Some((39, 60)));
+        assert_eq!(crt([(-1i128, 3), (-1, 5)]), Some((14, 15)));
+        assert_eq!(crt(Vec::<(u64, u64)>::new()), Some((0, 1)));
+
+        // moduli sharing factors
+        assert_eq!(crt([(3u64, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(3u64, 4), (4, 6)]), None);
+        assert_eq!(crt([(1u64, 0)]), None);
+
+        // bus schedule style system with a large combined modulus
+        let buses = [(0u128, 17), (13 - 2, 13), (19 - 3, 19)];
+        assert_eq!(crt(buses), Some((3417, 4199)));
+
+        let p = (1u128 << 61) - 1;
+        let q = (1u128 << 31) - 1;
+        let (x, m) = crt([(5u128, p), (7, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (5, 7));
+
+        assert_eq!(crt([(1u64, u64::MAX), (0, u64::MAX - 1)]), None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Regex based parsing of puzzle input into typed fields.
+
+use regex::{Captures, Regex};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Compiles a regex on first use and keeps it for later calls.
+///
+/// ```
+/// let re = aoc2022::regex!(r"^(?P<x>\d+),(?P<y>\d+)$");
+/// assert!(re.is_match("3,4"));
+/// ```
+#[macro_export]
+macro_rules! regex {
+    ($re:expr $(,)?) => {{
+        static RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| regex::Regex::new($re).unwrap())
+    }};
+}
+
+/// Named captures of a regex match with typed access. Errors are
+/// positioned at the offending capture, relative to the matched input.
+#[derive(Debug)]
+pub struct Fields<'t> {
+    input: &'t str,
+    caps: Captures<'t>,
+}
+
+impl<'t> Fields<'t> {
+    /// Matches `re` on `input`, failing with `reason` if it doesn't.
+    pub fn capture(re: &Regex, input: &'t str, reason: &str) -> Result<Self> {
+        let caps = re
+            .captures(input)
+            .ok_or_else(|| Error::parse(1, 1, reason))?;
+        Ok(Fields { input, caps })
+    }
+
+    /// Text of a capture, `None` if it didn't participate in the match.
+    pub fn text(&self, name: &str) -> Option<&'t str> {
+        self.caps.name(name).map(|m| m.as_str())
+    }
+
+    /// Converts a capture, failing at its start with "Invalid {name}
+    /// '{text}'" if that fails or at the end of the match with "Missing
+    /// {name}" if the capture is missing.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T> {
+        self.get_with(name, None)
+    }
+
+    /// Like [`Fields::get`] for an optional capture.
+    pub fn get_opt<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        self.convert(name, None)
+    }
+
+    /// Like [`Fields::get`], appending `reason` to the error message if
+    /// one is given.
+    pub fn get_with<T: FromStr>(&self, name: &str, reason: Option<&str>) -> Result<T> {
+        self.convert(name, reason)?
+            .ok_or_else(|| self.missing(name, reason))
+    }
+
+    /// Converts every item of a capture holding a list, failing with
+    /// "Invalid item '{item}' in {name}" at the offending item or at the
+    /// end of the match if the capture is missing.
+    pub fn list<T: FromStr, C: FromIterator<T>>(&self, name: &str, separator: &str) -> Result<C> {
+        let m = self
+            .caps
+            .name(name)
+            .ok_or_else(|| self.missing(name, None))?;
+
+        let mut offset = m.start();
+        m.as_str()
+            .split(separator)
+            .map(|item| {
+                let start = offset;
+                offset += item.len() + separator.len();
+                item.parse().map_err(|_| {
+                    let reason = format!("Invalid item '{}' in {}", item, name);
+                    Error::parse_at(self.input, start, reason)
+                })
+            })
+            .collect()
+    }
+
+    fn convert<T: FromStr>(&self, name: &str, reason: Option<&str>) -> Result<Option<T>> {
+        self.caps
+            .name(name)
+            .map(|m| {
+                m.as_str().parse().map_err(|_| {
+                    let message = format!("Invalid {} '{}'", name, m.as_str());
+                    Error::parse_at(self.input, m.start(), explain(message, reason))
+                })
+            })
+            .transpose()
+    }
+
+    fn missing(&self, name: &str, reason: Option<&str>) -> Error {
+        let end = self.caps.get(0).map_or(0, |m| m.end());
+        let message = format!("Missing {}", name);
+        Error::parse_at(self.input, end, explain(message, reason))
+    }
+
+    /// Error at the start of a capture, e.g. for a value that parses but
+    /// isn't valid. Falls back to the start of the input.
+    pub fn error(&self, name: &str, reason: impl Into<String>) -> Error {
+        let offset = self.caps.name(name).map_or(0, |m| m.start());
+        Error::parse_at(self.input, offset, reason)
+    }
+}
+
+fn explain(message: String, reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("{}: {}", message, reason),
+        None => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(input: &str) -> Result<Fields<'_>> {
+        let re = regex!(r"^(?P<name>\w+)(?: x(?P<count>\S+))?(?:: (?P<items>.*))?$");
+        Fields::capture(re, input, "Couldn't parse entry")
+    }
+
+    #[test]
+    fn typed_fields() {
+        let entry = fields("apples x12: 1, 2, 3").unwrap();
+        assert_eq!(entry.text("name"), Some("apples"));
+        assert_eq!(entry.get::<String>("name"), Ok("apples".to_owned()));
+        assert_eq!(entry.get("count"), Ok(12u8));
+        assert_eq!(entry.list("items", ", "), Ok(vec![1u32, 2, 3]));
+
+        let entry = fields("pears").unwrap();
+        assert_eq!(entry.text("count"), None);
+        assert_eq!(entry.get_opt::<u8>("count"), Ok(None));
+    }
+
+    #[test]
+    fn field_errors() {
+        assert_eq!(
+            fields("").unwrap_err(),
+            Error::parse(1, 1, "Couldn't parse entry")
+        );
+
+        let entry = fields("apples x300: 1, b, 3").unwrap();
+        assert_eq!(
+            entry.get::<u8>("count"),
+            Err(Error::parse(1, 9, "Invalid count '300'"))
+        );
+        assert_eq!(
+            entry.get_with::<u8>("count", Some("at most 255")),
+            Err(Error::parse(1, 9, "Invalid count '300': at most 255"))
+        );
+        assert_eq!(
+            entry.list::<u32, Vec<_>>("items", ", "),
+            Err(Error::parse(1, 17, "Invalid item 'b' in items"))
+        );
+        assert_eq!(
+            entry.error("name", "Unknown fruit"),
+            Error::parse(1, 1, "Unknown fruit")
+        );
+
+        let entry = fields("pears").unwrap();
+        assert_eq!(
+            entry.get::<u8>("count"),
+            Err(Error::parse(1, 6, "Missing count"))
+        );
+        assert_eq!(
+            entry.list::<u32, Vec<_>>("items", ", "),
+            Err(Error::parse(1, 6, "Missing items"))
+        );
+    }
+
+    #[test]
+    fn regex_compiled_once() {
+        let first = || regex!(r"\d+") as *const Regex;
+        assert_eq!(first(), first());
+    }
+}
@@ -0,0 +1,641 @@
+use num_traits::int::PrimInt;
+use num_traits::{NumCast, Signed};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Point on a plane, `y` grows towards [`Direction::North`].
+///
+/// Signed points span infinite planes, unsigned ones index grids where
+/// `x` is the column and `y` the row, so `y` grows downwards when the
+/// grid is printed. [`Point2D::try_cast`] and the `(row, col)`
+/// conversions move points between both.
+#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+pub struct Point2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2D<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2D { x, y }
+    }
+}
+
+impl<T: PrimInt> Point2D<T> {
+    /// Converts both coordinates, `None` if one is out of range of `U`.
+    pub fn try_cast<U: PrimInt>(self) -> Option<Point2D<U>> {
+        Some(Point2D::new(
+            <U as NumCast>::from(self.x)?,
+            <U as NumCast>::from(self.y)?,
+        ))
+    }
+
+    /// Point of an ndarray `(row, col)` index.
+    pub fn from_index((row, col): (usize, usize)) -> Option<Self> {
+        Point2D::new(col, row).try_cast()
+    }
+
+    /// ndarray `(row, col)` index of the point, `None` if negative.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        let Point2D { x: col, y: row } = self.try_cast()?;
+        Some((row, col))
+    }
+
+    /// Moves the point by a possibly signed `offset`, `None` if the
+    /// result is out of range of `T`, e.g. below zero for grid indices.
+    pub fn checked_offset<U: PrimInt>(self, offset: Point2D<U>) -> Option<Self> {
+        let point: Point2D<i128> = self.try_cast()?;
+        let offset: Point2D<i128> = offset.try_cast()?;
+        Point2D::new(
+            point.x.checked_add(offset.x)?,
+            point.y.checked_add(offset.y)?,
+        )
+        .try_cast()
+    }
+
+    /// Whether `other` is this point or one of its eight neighbors.
+    pub fn is_moore(&self, other: &Point2D<T>) -> bool {
+        self.x <= T::saturating_add(other.x, T::one())
+            && self.x >= T::saturating_sub(other.x, T::one())
+            && self.y <= T::saturating_add(other.y, T::one())
+            && self.y >= T::saturating_sub(other.y, T::one())
+    }
+}
+
+impl Point2D<usize> {
+    /// ndarray `(row, col)` index of a grid point.
+    pub fn index(self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+}
+
+impl<T: PrimInt + Signed> Point2D<T> {
+    pub fn manhattan(&self, other: &Point2D<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Number of king moves between both points.
+    pub fn chebyshev(&self, other: &Point2D<T>) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Sign of both coordinates, i.e. a single step towards this point
+    /// when it is seen as a distance.
+    pub fn signum(&self) -> Self {
+        Point2D::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The four orthogonal neighbors, starting north and going clockwise.
+    pub fn von_neumann_neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        Direction::ORTHOGONAL
+            .into_iter()
+            .map(move |direction| point + direction.unit())
+    }
+
+    /// All eight neighbors, starting north and going clockwise.
+    pub fn moore_neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| point + direction.unit())
+    }
+}
+
+impl<T: PrimInt> Default for Point2D<T> {
+    fn default() -> Self {
+        Point2D {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2D<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2D::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2D<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2D::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2D<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point2D::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2D<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2D<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2D<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point2D::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Point2D<T> {
+    type Output = Self;
+
+    fn div(self, divisor: T) -> Self {
+        Point2D::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+/// Compass direction on the plane of [`Point2D`].
+#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Directions of the von Neumann neighborhood, clockwise.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Directions of the Moore neighborhood, clockwise.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Step of length one (per axis) in this direction.
+    pub fn unit<T: PrimInt + Signed>(self) -> Point2D<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let (x, y) = match self {
+            Direction::North => (zero, one),
+            Direction::NorthEast => (one, one),
+            Direction::East => (one, zero),
+            Direction::SouthEast => (one, -one),
+            Direction::South => (zero, -one),
+            Direction::SouthWest => (-one, -one),
+            Direction::West => (-one, zero),
+            Direction::NorthWest => (-one, one),
+        };
+        Point2D::new(x, y)
+    }
+
+    /// Like [`Direction::unit`] for grid points, whose rows grow
+    /// towards the south.
+    pub fn grid_unit<T: PrimInt + Signed>(self) -> Point2D<T> {
+        let Point2D { x, y } = self.unit::<T>();
+        Point2D::new(x, -y)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    /// Turns clockwise by 45 degrees per step.
+    fn turn(self, steps: usize) -> Self {
+        let idx = Direction::ALL.iter().position(|d| *d == self).unwrap();
+        Direction::ALL[(idx + steps) % 8]
+    }
+
+    /// Turns clockwise by 90 degrees.
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Turns counterclockwise by 90 degrees.
+    pub fn turn_left(self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn is_orthogonal(self) -> bool {
+        Direction::ORTHOGONAL.contains(&self)
+    }
+}
+
+/// Point in space, e.g. a unit cube.
+#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone, Default)]
+pub struct Point3D<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3D<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3D { x, y, z }
+    }
+}
+
+impl<T: PrimInt> Point3D<T> {
+    /// Whether `other` is this point or one of its 26 neighbors.
+    pub fn is_moore(&self, other: &Point3D<T>) -> bool {
+        PointN::from(*self).is_moore(&PointN::from(*other))
+    }
+}
+
+impl<T: PrimInt + Signed> Point3D<T> {
+    pub fn manhattan(&self, other: &Point3D<T>) -> T {
+        PointN::from(*self).manhattan(&PointN::from(*other))
+    }
+
+    pub fn chebyshev(&self, other: &Point3D<T>) -> T {
+        PointN::from(*self).chebyshev(&PointN::from(*other))
+    }
+
+    /// The six neighbors sharing a face.
+    pub fn von_neumann_neighbors(&self) -> impl Iterator<Item = Self> {
+        PointN::from(*self)
+            .von_neumann_neighbors()
+            .map(Point3D::from)
+    }
+
+    /// All 26 neighbors sharing a face, an edge or a corner.
+    pub fn moore_neighbors(&self) -> impl Iterator<Item = Self> {
+        PointN::from(*self).moore_neighbors().map(Point3D::from)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3D<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3D::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3D<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3D::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3D<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3D::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> From<Point3D<T>> for PointN<T, 3> {
+    fn from(Point3D { x, y, z }: Point3D<T>) -> Self {
+        PointN::new([x, y, z])
+    }
+}
+
+impl<T> From<PointN<T, 3>> for Point3D<T> {
+    fn from(PointN { coords: [x, y, z] }: PointN<T, 3>) -> Self {
+        Point3D::new(x, y, z)
+    }
+}
+
+/// Point with `N` coordinates, for puzzles beyond three dimensions.
+#[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+pub struct PointN<T, const N: usize> {
+    pub coords: [T; N],
+}
+
+impl<T, const N: usize> PointN<T, N> {
+    pub const fn new(coords: [T; N]) -> Self {
+        PointN { coords }
+    }
+}
+
+impl<T: PrimInt, const N: usize> PointN<T, N> {
+    /// Whether `other` is this point or differs by at most one in every
+    /// coordinate.
+    pub fn is_moore(&self, other: &PointN<T, N>) -> bool {
+        self.coords.iter().zip(other.coords).all(|(a, b)| {
+            *a <= T::saturating_add(b, T::one()) && *a >= T::saturating_sub(b, T::one())
+        })
+    }
+}
+
+impl<T: PrimInt + Signed, const N: usize> PointN<T, N> {
+    pub fn manhattan(&self, other: &PointN<T, N>) -> T {
+        (*self - *other)
+            .coords
+            .iter()
+            .fold(T::zero(), |sum, coord| sum + coord.abs())
+    }
+
+    pub fn chebyshev(&self, other: &PointN<T, N>) -> T {
+        (*self - *other)
+            .coords
+            .iter()
+            .fold(T::zero(), |max, coord| max.max(coord.abs()))
+    }
+
+    pub fn signum(&self) -> Self {
+        PointN::new(self.coords.map(|coord| coord.signum()))
+    }
+
+    /// The `2 * N` neighbors differing in a single coordinate.
+    pub fn von_neumann_neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        (0..N).flat_map(move |axis| {
+            [-T::one(), T::one()].into_iter().map(move |delta| {
+                let mut neighbor = point;
+                neighbor.coords[axis] = neighbor.coords[axis] + delta;
+                neighbor
+            })
+        })
+    }
+
+    /// The `3^N - 1` neighbors differing by at most one in every
+    /// coordinate.
+    pub fn moore_neighbors(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        let count = 3usize.pow(N as u32);
+        // every offset is a number in base 3, digits 0, 1, 2 mean -1, 0, 1
+        (0..count)
+            .filter(move |offset| *offset != count / 2)
+            .map(move |mut offset| {
+                let mut neighbor = point;
+                for coord in neighbor.coords.iter_mut() {
+                    *coord = *coord + T::from(offset % 3).unwrap() - T::one();
+                    offset /= 3;
+                }
+                neighbor
+            })
+    }
+}
+
+impl<T: PrimInt, const N: usize> Default for PointN<T, N> {
+    fn default() -> Self {
+        PointN::new([T::zero(); N])
+    }
+}
+
+impl<T: Add<Output = T> + Copy, const N: usize> Add for PointN<T, N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (coord, other) in self.coords.iter_mut().zip(other.coords) {
+            *coord = *coord + other;
+        }
+        self
+    }
+}
+
+impl<T: Sub<Output = T> + Copy, const N: usize> Sub for PointN<T, N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (coord, other) in self.coords.iter_mut().zip(other.coords) {
+            *coord = *coord - other;
+        }
+        self
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        PointN::new(self.coords.map(|coord| -coord))
+    }
+}
+
+impl<T: Mul<Output = T> + Copy, const N: usize> Mul<T> for PointN<T, N> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        PointN::new(self.coords.map(|coord| coord * factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn point_moore_signed() {
+        let a: Point2D<i32> = Point2D { x: 0, y: 0 };
+        let b: Point2D<i32> = Point2D { x: 1, y: 0 };
+        let c: Point2D<i32> = Point2D { x: -1, y: -1 };
+        let d: Point2D<i32> = Point2D { x: 0, y: 1 };
+
+        assert!(a.is_moore(&b));
+        assert!(a.is_moore(&c));
+        assert!(!b.is_moore(&c));
+        assert!(b.is_moore(&d));
+
+        // symmetric
+        assert!(b.is_moore(&a));
+        assert!(c.is_moore(&a));
+        assert!(!c.is_moore(&b));
+        assert!(d.is_moore(&b));
+    }
+
+    #[test]
+    fn point_moore_unsigned() {
+        let a: Point2D<u32> = Point2D { x: 0, y: 0 };
+        let b: Point2D<u32> = Point2D { x: 1, y: 0 };
+        let c: Point2D<u32> = Point2D { x: 2, y: 2 };
+        let d: Point2D<u32> = Point2D { x: 0, y: 1 };
+
+        assert!(a.is_moore(&b));
+        assert!(!a.is_moore(&c));
+        assert!(!b.is_moore(&c));
+        assert!(b.is_moore(&d));
+
+        // symmetric
+        assert!(b.is_moore(&a));
+        assert!(!c.is_moore(&a));
+        assert!(!c.is_moore(&b));
+        assert!(d.is_moore(&b));
+    }
+
+    #[test]
+    fn point_default() {
+        let a: Point2D<i32> = Point2D::default();
+        assert_eq!(a, Point2D { x: 0, y: 0 });
+
+        let a: Point2D<u8> = Point2D::default();
+        assert_eq!(a, Point2D { x: 0, y: 0 });
+
+        let a: Point2D<usize> = Point2D::default();
+        assert_eq!(a, Point2D { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point2D::new(3, -2);
+        let b = Point2D::new(-1, 5);
+
+        assert_eq!(a + b, Point2D::new(2, 3));
+        assert_eq!(a - b, Point2D::new(4, -7));
+        assert_eq!(-a, Point2D::new(-3, 2));
+        assert_eq!(a * 3, Point2D::new(9, -6));
+        assert_eq!(Point2D::new(9, -6) / 3, a);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, Point2D::new(2, 3));
+        c -= b;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn point_distances() {
+        let a = Point2D::new(1, 1);
+        let b = Point2D::new(-2, 5);
+
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(b.chebyshev(&a), 4);
+        assert_eq!((b - a).signum(), Point2D::new(-1, 1));
+        assert_eq!(Point2D::new(0, -7).signum(), Point2D::new(0, -1));
+    }
+
+    #[test]
+    fn point_neighbors() {
+        let a = Point2D::new(0, 0);
+        assert_eq!(
+            a.von_neumann_neighbors().collect::<Vec<_>>(),
+            vec![
+                Point2D::new(0, 1),
+                Point2D::new(1, 0),
+                Point2D::new(0, -1),
+                Point2D::new(-1, 0)
+            ]
+        );
+
+        let neighbors: Vec<Point2D<i64>> = a.moore_neighbors().collect();
+        assert_eq!(neighbors.len(), 8);
+        assert!(neighbors.iter().all(|n| n.chebyshev(&a) == 1));
+    }
+
+    #[test]
+    fn point_conversions() {
+        let a: Point2D<i64> = Point2D::new(3, 7);
+        assert_eq!(a.try_cast::<usize>(), Some(Point2D::new(3, 7)));
+        assert_eq!(Point2D::new(-1i64, 7).try_cast::<usize>(), None);
+        assert_eq!(Point2D::new(300u32, 7).try_cast::<u8>(), None);
+
+        assert_eq!(a.to_index(), Some((7, 3)));
+        assert_eq!(Point2D::new(-3i32, 0).to_index(), None);
+        assert_eq!(Point2D::<u8>::from_index((2, 4)), Some(Point2D::new(4, 2)));
+        assert_eq!(Point2D::<u8>::from_index((256, 4)), None);
+        assert_eq!(Point2D::new(4usize, 2).index(), (2, 4));
+    }
+
+    #[test]
+    fn point_checked_offset() {
+        let a: Point2D<usize> = Point2D::new(0, 2);
+        assert_eq!(
+            a.checked_offset(Direction::East.unit::<i32>()),
+            Some(Point2D::new(1, 2))
+        );
+        assert_eq!(a.checked_offset(Direction::West.unit::<i32>()), None);
+        assert_eq!(
+            Point2D::new(i8::MAX, 0).checked_offset(Point2D::new(1, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::North.unit(), Point2D::new(0, 1));
+        assert_eq!(Direction::SouthWest.unit(), Point2D::new(-1, -1));
+        assert_eq!(Direction::North.grid_unit(), Point2D::new(0, -1));
+        assert_eq!(Direction::SouthEast.grid_unit(), Point2D::new(1, 1));
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert_eq!(Direction::NorthWest.opposite(), Direction::SouthEast);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert!(Direction::South.is_orthogonal());
+        assert!(!Direction::NorthEast.is_orthogonal());
+
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.unit::<i32>() + direction.opposite().unit(),
+                Point2D::new(0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn point_3d() {
+        let a = Point3D::new(1, 2, 3);
+        let b = Point3D::new(2, 2, 4);
+        let c = Point3D::new(3, 2, 3);
+
+        assert!(a.is_moore(&b));
+        assert!(!a.is_moore(&c));
+        assert_eq!(a.manhattan(&c), 2);
+        assert_eq!(a.chebyshev(&c), 2);
+        assert_eq!(a + b - a, b);
+        assert_eq!(-a, Point3D::new(-1, -2, -3));
+
+        let faces: HashSet<Point3D<i32>> = a.von_neumann_neighbors().collect();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|n| n.manhattan(&a) == 1));
+        assert!(faces.contains(&Point3D::new(1, 2, 4)));
+
+        let moore: HashSet<Point3D<i32>> = a.moore_neighbors().collect();
+        assert_eq!(moore.len(), 26);
+        assert!(!moore.contains(&a));
+        assert!(moore.iter().all(|n| n.is_moore(&a)));
+    }
+
+    #[test]
+    fn point_n() {
+        let a = PointN::new([0i64, 0, 0, 0]);
+        let b = PointN::new([1, -2, 0, 3]);
+
+        assert_eq!(a.manhattan(&b), 6);
+        assert_eq!(b.chebyshev(&a), 3);
+        assert_eq!(b.signum(), PointN::new([1, -1, 0, 1]));
+        assert_eq!(b * 2 - b, b);
+        assert_eq!(PointN::default(), a);
+        assert!(!a.is_moore(&b));
+        assert!(a.is_moore(&b.signum()));
+
+        assert_eq!(a.von_neumann_neighbors().count(), 8);
+        let moore: HashSet<_> = a.moore_neighbors().collect();
+        assert_eq!(moore.len(), 80);
+        assert!(moore.contains(&PointN::new([-1, 1, 0, -1])));
+
+        assert!(PointN::new([0u8, 5]).is_moore(&PointN::new([1, 4])));
+        assert_eq!(PointN::from(Point3D::new(1, 2, 3)), PointN::new([1, 2, 3]));
+    }
+}
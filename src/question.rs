@@ -1,54 +1,34 @@
-use ndarray::{Array1, Array2};
+use ndarray::Array2;
 use std::str::FromStr;
 
-#[derive(PartialEq, Eq, Copy, Clone, Debug)]
-struct Point2D {
-    x: usize,
-    y: usize,
-}
+use crate::error::{Error, Result};
+use crate::grid::{cell_error, Grid};
+use crate::point::Point2D;
 
 #[derive(PartialEq, Debug)]
 struct Field {
     heights: Array2<u8>,
-    point: Point2D,
+    point: Point2D<usize>,
 }
 
 impl FromStr for Field {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines().peekable();
-        let cols = lines.peek().ok_or("No input")?.len();
-
-        let mut point: Option<Point2D> = None;
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let mut point: Option<Point2D<usize>> = None;
 
-        let mut char_parse = |(row, col), chr| match chr {
+        let heights = Grid::parse(s, |pos, chr| match chr {
             c if c.is_ascii_lowercase() => Ok((c as u8) - 97),
             'P' => match point {
-                Some(_) => Err("Duplicate point"),
+                Some(_) => Err(cell_error(pos, "Duplicate point")),
                 None => {
-                    point = Some(Point2D { x: col, y: row });
+                    point = Some(pos);
                     Ok(0)
                 }
             },
-            _ => Err("Unsupported symbol"),
-        };
-
-        // This does not compile:
-        let flat_heights: Array1<u8> = lines
-            .enumerate()
-            .flat_map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(col, chr)| char_parse((row, col), chr))
-            })
-            .collect::<Result<_, _>>()?;
-
-        let rows = flat_heights.len() / cols;
-
-        let heights = flat_heights
-            .into_shape((rows, cols))
-            .map_err(|_| "Invalid shape")?;
-        let point = point.ok_or("No point found")?;
+            _ => Err(cell_error(pos, "Unsupported symbol")),
+        })?
+        .into_array();
+        let point = point.ok_or_else(|| Error::parse(1, 1, "No point found"))?;
 
         Ok(Field { heights, point })
     }
@@ -61,7 +41,7 @@ mod tests {
 
     #[test]
     fn parse_heights() {
-        let field: Result<Field, _> = "cab\nabP\nacc".parse();
+        let field: Result<Field> = "cab\nabP\nacc".parse();
         assert_eq!(
             field,
             Ok(Field {
@@ -70,4 +50,13 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn parse_heights_invalid() {
+        let field: Result<Field> = "cab\nPbP\nacc".parse();
+        assert_eq!(field, Err(Error::parse(2, 3, "Duplicate point")));
+
+        let field: Result<Field> = "cab\nab1\nacc".parse();
+        assert_eq!(field, Err(Error::parse(2, 3, "Unsupported symbol")));
+    }
 }
@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// Plain text table whose cells may span multiple lines.
+#[derive(Debug, Default)]
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Table {
+            header: header.iter().map(|cell| cell.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.header.len()];
+        for row in std::iter::once(&self.header).chain(self.rows.iter()) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                let cell_width = cell.lines().map(|l| l.chars().count()).max();
+                *width = (*width).max(cell_width.unwrap_or(0));
+            }
+        }
+        widths
+    }
+}
+
+fn write_row(f: &mut fmt::Formatter<'_>, widths: &[usize], row: &[String]) -> fmt::Result {
+    let cells: Vec<Vec<&str>> = row.iter().map(|cell| cell.lines().collect()).collect();
+    let height = cells.iter().map(|lines| lines.len()).max().unwrap_or(0);
+
+    for line in 0..height.max(1) {
+        let text: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, width)| {
+                let text = cells
+                    .get(col)
+                    .and_then(|lines| lines.get(line))
+                    .unwrap_or(&"");
+                format!("{:width$}", text, width = width)
+            })
+            .collect();
+        writeln!(f, "{}", text.join(" | ").trim_end())?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+
+        write_row(f, &widths, &self.header)?;
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        writeln!(f, "{}", rule.join("-+-"))?;
+
+        for row in &self.rows {
+            write_row(f, &widths, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn render_table() {
+        let mut table = Table::new(&["Day", "Part one", "Part two"]);
+        table.row(vec!["1".into(), "24000".into(), "45000".into()]);
+        table.row(vec!["10".into(), "13140".into(), "#..#\n.##.".into()]);
+        table.row(vec!["12".into(), "error: foo".into()]);
+
+        assert_eq!(
+            table.to_string(),
+            indoc! {"
+                Day | Part one   | Part two
+                ----+------------+---------
+                1   | 24000      | 45000
+                10  | 13140      | #..#
+                    |            | .##.
+                12  | error: foo |
+            "}
+        );
+    }
+}
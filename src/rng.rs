@@ -0,0 +1,81 @@
+//! Small seeded random number generator, so simulations are
+//! reproducible without pulling in a dependency.
+
+/// SplitMix64 generator: fast, tiny state and good enough statistics for
+/// simulations. Not suitable for anything security related.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed in `0..bound`, panics for a bound of 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Empty range");
+        // reject the top values that would make the lower ones more likely
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Random element of a slice, `None` if it's empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_values() {
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+        assert_eq!(rng.next_u64(), 0x06C4_5D18_8009_454F);
+    }
+
+    #[test]
+    fn seeded() {
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(values(42), values(42));
+        assert_ne!(values(42), values(43));
+    }
+
+    #[test]
+    fn below() {
+        let mut rng = Rng::new(7);
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[rng.below(3) as usize] += 1;
+        }
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+        assert_eq!(rng.below(1), 0);
+
+        assert_eq!(rng.choose::<u8>(&[]), None);
+        assert_eq!(rng.choose(&['a']), Some(&'a'));
+    }
+}
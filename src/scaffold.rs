@@ -0,0 +1,283 @@
+//! Sources and registry edits to start a new day, see `aoc2022 new`.
+
+const ONES: [&str; 10] = [
+    "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// Snake case name of a day of the advent, e.g. `twenty_five`.
+pub fn number_word(day: u32) -> Option<String> {
+    let word = match day {
+        1..=9 => ONES[day as usize].to_owned(),
+        10..=19 => TEENS[day as usize - 10].to_owned(),
+        20 => "twenty".to_owned(),
+        21..=25 => format!("twenty_{}", ONES[day as usize - 20]),
+        _ => return None,
+    };
+    Some(word)
+}
+
+/// Inverse of [`number_word`].
+fn word_number(word: &str) -> Option<u32> {
+    (1..=25).find(|day| number_word(*day).as_deref() == Some(word))
+}
+
+/// Module name, e.g. `day_twelve`.
+pub fn module_name(day: u32) -> Option<String> {
+    Some(format!("day_{}", number_word(day)?))
+}
+
+/// Name of the type implementing `Solution`, e.g. `DayTwelve`.
+pub fn type_name(day: u32) -> Option<String> {
+    let name = number_word(day)?
+        .split('_')
+        .map(|word| word[..1].to_uppercase() + &word[1..])
+        .collect::<String>();
+    Some(format!("Day{}", name))
+}
+
+/// Skeleton of `src/day_*.rs` with both parts still `todo!()`.
+pub fn module_source(day: u32) -> Option<String> {
+    let type_name = type_name(day)?;
+    Some(format!(
+        r#"use crate::answer::Answer;
+use crate::error::Result;
+use crate::solution::{{self, Solution}};
+
+pub fn both(input: &str) -> Result<(Answer, Answer)> {{
+    solution::both::<{type_name}>(input)
+}}
+
+pub fn part_one(_input: &str) -> Result<u32> {{
+    todo!();
+}}
+
+pub fn part_two(_input: &str) -> Result<u32> {{
+    todo!();
+}}
+
+pub struct {type_name};
+
+impl Solution for {type_name} {{
+    const DAY: u32 = {day};
+
+    type Input = String;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<String> {{
+        Ok(input.to_owned())
+    }}
+
+    fn part_one(input: &String) -> Result<u32> {{
+        part_one(input)
+    }}
+
+    fn part_two(input: &String) -> Result<u32> {{
+        part_two(input)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {{"
+        TODO paste the example input
+    "}};
+
+    #[test]
+    fn parse_example() {{
+        assert!({type_name}::parse(EXAMPLE).is_ok());
+    }}
+}}
+"#
+    ))
+}
+
+/// Skeleton of `tests/day_*.rs`, ignored until the example is filled in.
+pub fn test_source(day: u32) -> Option<String> {
+    let module_name = module_name(day)?;
+    let test = |part: &str| {
+        format!(
+            r#"
+#[test]
+#[ignore = "example not filled in yet"]
+fn {part}_examples() {{
+    let puzzle_input = indoc! {{"
+        TODO paste the example input
+    "}};
+    assert_eq!({part}(puzzle_input), Ok(0));
+}}
+"#
+        )
+    };
+    Some(format!(
+        "use aoc2022::{}::{{part_one, part_two}};\nuse indoc::indoc;\n{}{}",
+        module_name,
+        test("part_one"),
+        test("part_two")
+    ))
+}
+
+/// Adds the `pub mod` line of the day to `src/lib.rs`, keeping the
+/// module declarations sorted.
+pub fn register_module(lib_rs: &str, day: u32) -> Option<String> {
+    let line = format!("pub mod {};", module_name(day)?);
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    if lines.contains(&line.as_str()) {
+        return None;
+    }
+
+    let position = lines
+        .iter()
+        .position(|other| other.starts_with("pub mod ") && *other > line.as_str())
+        .or_else(|| {
+            let last = lines
+                .iter()
+                .rposition(|other| other.starts_with("pub mod "));
+            Some(last.map_or(0, |idx| idx + 1))
+        })?;
+    lines.insert(position, &line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Imports the type of the day in `src/solution.rs` and adds it to
+/// `DAYS`, which is ordered by day number.
+pub fn register_day(solution_rs: &str, day: u32) -> Option<String> {
+    let import = format!("    {}::{},", module_name(day)?, type_name(day)?);
+    let entry = format!("    Day::new::<{}>(),", type_name(day)?);
+    let mut lines: Vec<&str> = solution_rs.lines().collect();
+    if lines.contains(&entry.as_str()) {
+        return None;
+    }
+
+    // rustfmt keeps the imports of `use crate::{...}` sorted
+    let imports_start = lines.iter().position(|line| *line == "use crate::{")?;
+    let imports_end = imports_start + lines[imports_start..].iter().position(|l| *l == "};")?;
+    let position = (imports_start + 1..imports_end)
+        .find(|idx| lines[*idx] > import.as_str())
+        .unwrap_or(imports_end);
+    lines.insert(position, &import);
+
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))?;
+    let days_end = days_start + lines[days_start..].iter().position(|l| *l == "];")?;
+    let position = (days_start + 1..days_end)
+        .find(|idx| entry_day(lines[*idx]).is_some_and(|other| other > day))
+        .unwrap_or(days_end);
+    lines.insert(position, &entry);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Day number of a `DAYS` entry like `    Day::new::<DayTwelve>(),`.
+fn entry_day(line: &str) -> Option<u32> {
+    let name = line
+        .trim()
+        .strip_prefix("Day::new::<Day")?
+        .strip_suffix(">(),")?;
+    let word = name
+        .chars()
+        .enumerate()
+        .fold(String::new(), |mut word, (idx, chr)| {
+            if chr.is_uppercase() && idx > 0 {
+                word.push('_');
+            }
+            word.push(chr.to_ascii_lowercase());
+            word
+        });
+    word_number(&word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn names() {
+        assert_eq!(module_name(7).as_deref(), Some("day_seven"));
+        assert_eq!(module_name(13).as_deref(), Some("day_thirteen"));
+        assert_eq!(type_name(20).as_deref(), Some("DayTwenty"));
+        assert_eq!(type_name(24).as_deref(), Some("DayTwentyFour"));
+        assert_eq!(number_word(0), None);
+        assert_eq!(number_word(26), None);
+        assert_eq!(entry_day("    Day::new::<DayTwentyOne>(),"), Some(21));
+    }
+
+    #[test]
+    fn add_module() {
+        let lib_rs = indoc! {"
+            pub mod answer;
+            pub mod day_one;
+            pub mod day_twelve;
+            pub mod error;
+
+            use std::fmt;
+        "};
+        assert_eq!(
+            register_module(lib_rs, 3).unwrap(),
+            indoc! {"
+                pub mod answer;
+                pub mod day_one;
+                pub mod day_three;
+                pub mod day_twelve;
+                pub mod error;
+
+                use std::fmt;
+            "}
+        );
+        assert_eq!(register_module(lib_rs, 12), None);
+    }
+
+    #[test]
+    fn add_day() {
+        let solution_rs = indoc! {"
+            use crate::{
+                answer::Answer,
+                day_one::DayOne,
+                day_twelve::DayTwelve,
+                error::Result,
+            };
+
+            pub const DAYS: &[Day] = &[
+                Day::new::<DayOne>(),
+                Day::new::<DayTwelve>(),
+            ];
+        "};
+        assert_eq!(
+            register_day(solution_rs, 2).unwrap(),
+            indoc! {"
+                use crate::{
+                    answer::Answer,
+                    day_one::DayOne,
+                    day_twelve::DayTwelve,
+                    day_two::DayTwo,
+                    error::Result,
+                };
+
+                pub const DAYS: &[Day] = &[
+                    Day::new::<DayOne>(),
+                    Day::new::<DayTwo>(),
+                    Day::new::<DayTwelve>(),
+                ];
+            "}
+        );
+        assert_eq!(register_day(solution_rs, 1), None);
+    }
+}
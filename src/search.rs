@@ -0,0 +1,321 @@
+//! Shortest paths over graphs given by a successor function.
+
+use num_traits::Zero;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Outcome of a search: distances of all nodes whose shortest distance
+/// is known, the goal if one was reached and the predecessors to
+/// reconstruct paths.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    /// Reached goal, the nearest one if several match.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Distance to the reached goal.
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// Distance of a node from the nearest start, if known.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Distances of all nodes whose shortest distance is known. [`bfs`]
+    /// records nodes as soon as it discovers them, so this includes the
+    /// frontier left when it stopped at the goal. [`dijkstra`] and
+    /// [`astar`] only record nodes taken from their queue.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// Nodes from a start to the reached goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// Nodes from a start to a node with a known distance, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search counting steps, stops at the first node
+/// matching `is_goal`. Pass `|_| false` to get the distances of all
+/// reachable nodes.
+pub fn bfs<N, S>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> S,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for next in successors(&node) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance + 1);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm over non-negative edge costs, stops at the
+/// first node matching `is_goal`.
+pub fn dijkstra<N, C, S>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> S,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    S: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search, `heuristic` must be consistent for the found path to be
+/// the shortest: zero at goals and never dropping by more than the cost
+/// of a step, i.e. `heuristic(a) <= cost + heuristic(b)` for a step from
+/// `a` to `b`. Nodes are never reopened once taken from the queue, so a
+/// heuristic that's only admissible isn't enough.
+pub fn astar<N, C, S>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> S,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    S: IntoIterator<Item = (N, C)>,
+{
+    // nodes are kept in `nodes` so the heap doesn't require them to be `Ord`
+    let mut nodes: Vec<N> = Vec::new();
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut tentative: Vec<(C, Option<usize>)> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    let mut search = Search {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+
+    let mut id_of = |node: &N, nodes: &mut Vec<N>| -> (usize, bool) {
+        match ids.get(node) {
+            Some(id) => (*id, false),
+            None => {
+                ids.insert(node.clone(), nodes.len());
+                nodes.push(node.clone());
+                (nodes.len() - 1, true)
+            }
+        }
+    };
+
+    for start in starts {
+        let (id, new) = id_of(&start, &mut nodes);
+        if new {
+            tentative.push((C::zero(), None));
+            heap.push(Reverse((heuristic(&start), C::zero(), id)));
+        }
+    }
+
+    while let Some(Reverse((_, distance, id))) = heap.pop() {
+        let node = nodes[id].clone();
+        if search.distances.contains_key(&node) || distance > tentative[id].0 {
+            continue;
+        }
+
+        search.distances.insert(node.clone(), distance);
+        if let Some(parent) = tentative[id].1 {
+            search.parents.insert(node.clone(), nodes[parent].clone());
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, cost) in successors(&node) {
+            let next_distance = distance + cost;
+            let (next_id, new) = id_of(&next, &mut nodes);
+            if new {
+                tentative.push((next_distance, Some(id)));
+            } else if next_distance < tentative[next_id].0 {
+                tentative[next_id] = (next_distance, Some(id));
+            } else {
+                continue;
+            }
+            heap.push(Reverse((
+                next_distance + heuristic(&next),
+                next_distance,
+                next_id,
+            )));
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::point::Point2D;
+    use indoc::indoc;
+
+    /// Climbing up along the top and down the right side is the only
+    /// way from the start to the end, the 9s are too high.
+    const HEIGHTMAP: &str = indoc! {"
+        01234
+        99995
+        32106
+    "};
+
+    const START: Point2D<usize> = Point2D::new(0, 0);
+    const END: Point2D<usize> = Point2D::new(0, 2);
+
+    fn heightmap() -> Grid<u8> {
+        Grid::parse(HEIGHTMAP, |_, chr| Ok(chr.to_digit(10).unwrap() as u8)).unwrap()
+    }
+
+    fn climbable(map: &Grid<u8>, pos: Point2D<usize>) -> Vec<Point2D<usize>> {
+        map.neighbors(pos)
+            .filter(|next| map[*next] <= map[pos] + 1)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_heightmap() {
+        let map = heightmap();
+        let search = bfs([START], |pos| climbable(&map, *pos), |pos| *pos == END);
+        assert_eq!(search.goal(), Some(&END));
+        assert_eq!(search.cost(), Some(10));
+
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&START));
+        assert_eq!(path.last(), Some(&END));
+        assert!(path
+            .windows(2)
+            .all(|step| climbable(&map, step[0]).contains(&step[1])));
+    }
+
+    #[test]
+    fn bfs_multiple_starts() {
+        let map = heightmap();
+        let lowest = map.iter().filter(|(_, h)| **h == 0).map(|(pos, _)| pos);
+        let search = bfs(lowest, |pos| climbable(&map, *pos), |pos| *pos == END);
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.path().unwrap()[0], Point2D::new(3, 2));
+    }
+
+    #[test]
+    fn bfs_distance_map() {
+        let map = heightmap();
+        let search = bfs([START], |pos| climbable(&map, *pos), |_| false);
+        assert_eq!(search.goal(), None);
+        // all but the four 9s
+        assert_eq!(search.distances().len(), map.rows() * map.cols() - 4);
+        assert_eq!(search.distance(&END), Some(10));
+        assert_eq!(search.distance(&Point2D::new(0, 1)), None);
+        assert_eq!(search.path_to(&Point2D::new(1, 0)).unwrap().len(), 2);
+
+        let unreachable = bfs([START], |_| Vec::new(), |pos| *pos == END);
+        assert_eq!(unreachable.cost(), None);
+        assert_eq!(unreachable.path(), None);
+    }
+
+    /// Steps cost one more per level climbed, descending is free.
+    fn weighted(map: &Grid<u8>, pos: Point2D<usize>) -> Vec<(Point2D<usize>, u32)> {
+        climbable(map, pos)
+            .into_iter()
+            .map(|next| (next, 1 + map[next].saturating_sub(map[pos]) as u32))
+            .collect()
+    }
+
+    #[test]
+    fn dijkstra_heightmap() {
+        let map = heightmap();
+        let search = dijkstra([START], |pos| weighted(&map, *pos), |pos| *pos == END);
+        let path = search.path().unwrap();
+        let cost: u32 = path
+            .windows(2)
+            .map(|step| {
+                weighted(&map, step[0])
+                    .into_iter()
+                    .find(|(n, _)| *n == step[1])
+                    .unwrap()
+                    .1
+            })
+            .sum();
+        assert_eq!(search.cost(), Some(cost));
+        // 10 steps climbing 9 levels in total
+        assert_eq!(cost, 19);
+
+        let unit = dijkstra(
+            [START],
+            |pos| climbable(&map, *pos).into_iter().map(|n| (n, 1)),
+            |pos| *pos == END,
+        );
+        assert_eq!(unit.cost(), Some(10));
+    }
+
+    #[test]
+    fn astar_heightmap() {
+        let map = heightmap();
+        let to_end = |pos: &Point2D<usize>| {
+            let pos: Point2D<i64> = pos.try_cast().unwrap();
+            pos.manhattan(&END.try_cast().unwrap()) as u32
+        };
+        let search = astar(
+            [START],
+            |pos| weighted(&map, *pos),
+            to_end,
+            |pos| *pos == END,
+        );
+        assert_eq!(search.cost(), Some(19));
+        assert_eq!(search.path().unwrap().len(), 11);
+    }
+}
@@ -0,0 +1,275 @@
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::{
+    answer::Answer,
+    bench::{self, Bench},
+    day_eight::DayEight,
+    day_eleven::DayEleven,
+    day_five::DayFive,
+    day_four::DayFour,
+    day_nine::DayNine,
+    day_one::DayOne,
+    day_seven::DaySeven,
+    day_six::DaySix,
+    day_ten::DayTen,
+    day_three::DayThree,
+    day_twelve::DayTwelve,
+    day_two::DayTwo,
+    error::{Error, Result},
+};
+
+/// A puzzle of a single day, split into parsing the input and solving
+/// both parts on the parsed input.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    type PartOne: Into<Answer>;
+    type PartTwo: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+/// Type erased entry of the registry, so days with different
+/// answer types can be handled alike.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    run: fn(&str) -> Run,
+    bench: fn(&str, usize) -> Result<Bench>,
+}
+
+/// Answers to both parts of a day, each part fails on its own.
+pub type Parts = (Result<Answer>, Result<Answer>);
+
+/// Answer to a part along with the time spent solving it.
+#[derive(Debug, Clone)]
+pub struct Timed {
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+/// Both parts of a day solved on the same parsed input.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse_elapsed: Duration,
+    pub part_one: Timed,
+    pub part_two: Timed,
+}
+
+impl Run {
+    /// Run in which parsing, or reading the input, failed.
+    pub fn failed(err: Error, parse_elapsed: Duration) -> Self {
+        let timed = |answer| Timed {
+            answer,
+            elapsed: Duration::ZERO,
+        };
+        Run {
+            parse_elapsed,
+            part_one: timed(Err(err.clone())),
+            part_two: timed(Err(err)),
+        }
+    }
+
+    pub fn into_parts(self) -> Parts {
+        (self.part_one.answer, self.part_two.answer)
+    }
+}
+
+impl Day {
+    const fn new<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+        }
+    }
+
+    /// Solves both parts for the given puzzle input. A failing or
+    /// panicking part doesn't keep the other one from being solved.
+    pub fn parts(&self, input: &str) -> Parts {
+        self.run(input).into_parts()
+    }
+
+    /// Reads the whole puzzle input from `reader` and solves both parts,
+    /// a read error fails both parts.
+    pub fn parts_from(&self, reader: impl Read) -> Parts {
+        self.run_from(reader).into_parts()
+    }
+
+    /// Like [`Day::parts`], also measuring how long parsing and each
+    /// part took.
+    pub fn run(&self, input: &str) -> Run {
+        (self.run)(input)
+    }
+
+    /// Like [`Day::parts_from`], also measuring how long parsing and
+    /// each part took.
+    pub fn run_from(&self, reader: impl Read) -> Run {
+        match read_input(reader) {
+            Ok(input) => self.run(&input),
+            Err(err) => Run::failed(err, Duration::ZERO),
+        }
+    }
+
+    /// Times parsing and both parts over repeated runs.
+    pub fn bench(&self, input: &str, runs: usize) -> Result<Bench> {
+        (self.bench)(input, runs)
+    }
+}
+
+fn run<S: Solution>(input: &str) -> Run {
+    let start = Instant::now();
+    let input = catch(|| S::parse(input));
+    let parse_elapsed = start.elapsed();
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => return Run::failed(err, parse_elapsed),
+    };
+
+    let part_one = timed(|| S::part_one(&input).map(Into::into));
+    let part_two = timed(|| S::part_two(&input).map(Into::into));
+
+    Run {
+        parse_elapsed,
+        part_one,
+        part_two,
+    }
+}
+
+fn timed(part: impl FnOnce() -> Result<Answer>) -> Timed {
+    let start = Instant::now();
+    let answer = catch(part);
+    Timed {
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Turns a panic, e.g. of a `todo!()`, into an error.
+pub(crate) fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let reason = match payload.downcast_ref::<&str>() {
+            Some(reason) => reason.to_string(),
+            None => match payload.downcast_ref::<String>() {
+                Some(reason) => reason.clone(),
+                None => "unknown reason".to_owned(),
+            },
+        };
+        Err(Error::Panicked(reason))
+    })
+}
+
+/// Parses the input once and solves both parts on it.
+pub fn both<S: Solution>(input: &str) -> Result<(Answer, Answer)> {
+    let input = S::parse(input)?;
+
+    let part_one_solution = S::part_one(&input)?.into();
+    let part_two_solution = S::part_two(&input)?.into();
+
+    Ok((part_one_solution, part_two_solution))
+}
+
+/// Like [`both`], with the puzzle input read from `reader`.
+pub fn both_from<S: Solution>(reader: impl Read) -> Result<(Answer, Answer)> {
+    both::<S>(&read_input(reader)?)
+}
+
+/// Reads a whole puzzle input, e.g. from stdin or an in-memory buffer.
+pub fn read_input(mut reader: impl Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// All implemented days, in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<DayOne>(),
+    Day::new::<DayTwo>(),
+    Day::new::<DayThree>(),
+    Day::new::<DayFour>(),
+    Day::new::<DayFive>(),
+    Day::new::<DaySix>(),
+    Day::new::<DaySeven>(),
+    Day::new::<DayEight>(),
+    Day::new::<DayNine>(),
+    Day::new::<DayTen>(),
+    Day::new::<DayEleven>(),
+    Day::new::<DayTwelve>(),
+];
+
+pub fn day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_ordered_and_unique() {
+        let numbers: Vec<u32> = DAYS.iter().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=DAYS.len() as u32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn catch_panic() {
+        let result: Result<u32> = catch(|| todo!());
+        assert_eq!(
+            result,
+            Err(Error::Panicked("not yet implemented".to_owned()))
+        );
+
+        let result: Result<u32> = catch(|| panic!("{} is out of range", 42));
+        assert_eq!(
+            result,
+            Err(Error::Panicked("42 is out of range".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parts_fail_independently() {
+        let (part_one, part_two) = day(6).unwrap().parts("abcd");
+        assert_eq!(part_one, Ok(Answer::Number(4)));
+        assert!(matches!(part_two, Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn parse_error_fails_both_parts() {
+        let (part_one, part_two) = day(10).unwrap().parts("noop\njump 3\n");
+        let err = Error::parse(2, 1, "Unknown instruction");
+        assert_eq!(part_one, Err(err.clone()));
+        assert_eq!(part_two, Err(err));
+    }
+
+    #[test]
+    fn parts_from_reader() {
+        let (part_one, part_two) = day(6).unwrap().parts_from("abcd".as_bytes());
+        assert_eq!(part_one, Ok(Answer::Number(4)));
+        assert!(part_two.is_err());
+
+        let (part_one, part_two) = day(6).unwrap().parts_from(&[0xff, 0xfe][..]);
+        assert!(matches!(part_one, Err(Error::Io(_))));
+        assert!(matches!(part_two, Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn both_from_reader() {
+        let input = "bvwbjplbgvbhsrlpgdmjqwftvncz".as_bytes();
+        assert_eq!(
+            both_from::<DaySix>(input),
+            Ok((Answer::Number(5), Answer::Number(23)))
+        );
+    }
+
+    #[test]
+    fn lookup_day() {
+        assert_eq!(day(7).map(|day| day.number), Some(7));
+        assert!(day(0).is_none());
+        assert!(day(26).is_none());
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use crate::point::Point2D;
+
+/// Cells on an unbounded plane, only occupied cells are stored.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2D<i64>, T>,
+    bounds: Option<(Point2D<i64>, Point2D<i64>)>,
+}
+
+/// Direction of the y axis when rendering, see [`Render`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum YAxis {
+    /// Rows with greater `y` are drawn above, like on a plane
+    Up,
+    /// Rows with greater `y` are drawn below, like on a screen
+    Down,
+}
+
+/// How a [`SparseGrid`] is drawn.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Render {
+    /// Glyph of unoccupied cells
+    pub empty: char,
+    pub y_axis: YAxis,
+    /// Glyph marking `(0, 0)` when unoccupied, which also extends the
+    /// drawn area to include it
+    pub origin: Option<char>,
+}
+
+impl Default for Render {
+    fn default() -> Self {
+        Render {
+            empty: '.',
+            y_axis: YAxis::Up,
+            origin: None,
+        }
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Smallest and largest coordinates of all occupied cells.
+    pub fn bounds(&self) -> Option<(Point2D<i64>, Point2D<i64>)> {
+        self.bounds
+    }
+
+    pub fn contains(&self, pos: Point2D<i64>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Point2D<i64>) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Point2D<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Occupies a cell, returns its previous value.
+    pub fn insert(&mut self, pos: Point2D<i64>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point2D::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Point2D::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+            None => (pos, pos),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point2D<i64>) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        // only cells on the edge of the bounding box may shrink it
+        if let Some((min, max)) = self.bounds {
+            if pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y {
+                self.bounds = bounds(self.cells.keys().copied());
+            }
+        }
+        Some(value)
+    }
+
+    /// Occupied cells from the smallest row to the largest, each row
+    /// from the smallest column.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D<i64>, &T)> {
+        let mut cells: Vec<_> = self
+            .cells
+            .iter()
+            .map(|(pos, value)| (*pos, value))
+            .collect();
+        cells.sort_unstable_by_key(|(pos, _)| (pos.y, pos.x));
+        cells.into_iter()
+    }
+
+    /// Draws the bounding box with one char per cell and a line per row.
+    pub fn render(&self, options: &Render, mut glyph: impl FnMut(&T) -> char) -> String {
+        let origin = options.origin.map(|_| Point2D::new(0, 0));
+        let corners = self.bounds.into_iter().flat_map(|(min, max)| [min, max]);
+        let Some((min, max)) = bounds(corners.chain(origin)) else {
+            return String::new();
+        };
+
+        let rows: Box<dyn Iterator<Item = i64>> = match options.y_axis {
+            YAxis::Up => Box::new((min.y..=max.y).rev()),
+            YAxis::Down => Box::new(min.y..=max.y),
+        };
+
+        let mut text = String::new();
+        for y in rows {
+            for x in min.x..=max.x {
+                let pos = Point2D::new(x, y);
+                text.push(match (self.get(pos), options.origin) {
+                    (Some(value), _) => glyph(value),
+                    (None, Some(origin)) if pos == Point2D::new(0, 0) => origin,
+                    (None, _) => options.empty,
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+fn bounds(points: impl Iterator<Item = Point2D<i64>>) -> Option<(Point2D<i64>, Point2D<i64>)> {
+    points.fold(None, |bounds, pos| {
+        Some(match bounds {
+            Some((min, max)) => (
+                Point2D::new(pos.x.min(min.x), pos.y.min(min.y)),
+                Point2D::new(pos.x.max(max.x), pos.y.max(max.y)),
+            ),
+            None => (pos, pos),
+        })
+    })
+}
+
+impl<T> Extend<(Point2D<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2D<i64>, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(Point2D<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2D<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn grid() -> SparseGrid<char> {
+        [
+            (Point2D::new(2, 1), 'a'),
+            (Point2D::new(-1, 1), 'b'),
+            (Point2D::new(0, -2), 'c'),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn bounds_tracking() {
+        let mut grid = grid();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2D::new(-1, -2), Point2D::new(2, 1)))
+        );
+
+        assert_eq!(grid.insert(Point2D::new(5, 0), 'd'), None);
+        assert_eq!(grid.insert(Point2D::new(5, 0), 'e'), Some('d'));
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2D::new(-1, -2), Point2D::new(5, 1)))
+        );
+
+        assert_eq!(grid.remove(Point2D::new(5, 0)), Some('e'));
+        assert_eq!(grid.remove(Point2D::new(0, -2)), Some('c'));
+        assert_eq!(grid.remove(Point2D::new(0, -2)), None);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2D::new(-1, 1), Point2D::new(2, 1)))
+        );
+
+        grid.remove(Point2D::new(2, 1));
+        grid.remove(Point2D::new(-1, 1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn iter_row_major() {
+        let cells: String = grid().iter().map(|(_, chr)| *chr).collect();
+        assert_eq!(cells, "cba");
+    }
+
+    #[test]
+    fn render() {
+        let grid = grid();
+        assert_eq!(
+            grid.render(&Render::default(), |chr| *chr),
+            indoc! {"
+                b..a
+                ....
+                ....
+                .c..
+            "}
+        );
+
+        let options = Render {
+            empty: ' ',
+            y_axis: YAxis::Down,
+            origin: Some('s'),
+        };
+        assert_eq!(grid.render(&options, |_| '#'), " #  \n    \n s  \n#  #\n");
+
+        let grid: SparseGrid<char> = [(Point2D::new(3, 2), 'x')].into_iter().collect();
+        let options = Render {
+            origin: Some('s'),
+            ..Render::default()
+        };
+        assert_eq!(
+            grid.render(&options, |chr| *chr),
+            indoc! {"
+                ...x
+                ....
+                s...
+            "}
+        );
+
+        let empty = SparseGrid::<char>::new();
+        assert_eq!(empty.render(&options, |chr| *chr), "s\n");
+        assert_eq!(empty.render(&Render::default(), |chr| *chr), "");
+    }
+}
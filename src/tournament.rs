@@ -0,0 +1,485 @@
+//! Rock paper scissors tournaments between strategies, scored like the
+//! strategy guides of day two under any of its rules. Every strategy plays every other one
+//! for a number of rounds, seeded so every run ends the same.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::day_two::{strategy_guide, Game, Outcome, Rules, Shape};
+use crate::error::{Error, Result};
+use crate::report::Table;
+use crate::rng::Rng;
+
+/// A player of a match. Each match starts from a fresh clone, so state
+/// only lasts for a single match.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Shape of `rules` for the next round, `history` holds the rounds
+    /// played so far from the side of this player.
+    fn play(&mut self, rules: &Rules, history: &[Game]) -> Shape;
+
+    fn clone_box(&self) -> Box<dyn Strategy>;
+}
+
+/// First shape beating `shape`, `shape` itself if none does.
+fn counter(rules: &Rules, shape: Shape) -> Shape {
+    rules.for_outcome(shape, Outcome::Win).unwrap_or(shape)
+}
+
+/// The most frequent of the shapes, the first one of the rules on a tie.
+fn most_frequent(rules: &Rules, shapes: impl Iterator<Item = Shape>) -> Option<Shape> {
+    let mut counts = vec![0; rules.shape_count()];
+    for shape in shapes {
+        counts[shape.0] += 1;
+    }
+    rules
+        .shapes()
+        .filter(|shape| counts[shape.0] > 0)
+        .max_by_key(|shape| (counts[shape.0], Reverse(shape.0)))
+}
+
+/// Plays a fixed list of shapes over and over, like a strategy guide
+/// followed without looking at the opponent.
+#[derive(Debug, Clone)]
+pub struct Guide {
+    name: String,
+    plays: Vec<Shape>,
+}
+
+impl Guide {
+    /// Fails without any plays or for a shape `rules` don't have, with
+    /// the plays taken as the lines of a guide.
+    pub fn new(rules: &Rules, name: &str, plays: Vec<Shape>) -> Result<Self> {
+        if plays.is_empty() {
+            return Err(Error::parse(1, 1, "Guide without plays"));
+        }
+        if let Some(idx) = plays
+            .iter()
+            .position(|shape| shape.0 >= rules.shape_count())
+        {
+            let reason = format!("Unknown shape {}", plays[idx].0);
+            return Err(Error::parse(idx + 1, 1, reason));
+        }
+        Ok(Guide {
+            name: name.to_owned(),
+            plays,
+        })
+    }
+}
+
+impl Strategy for Guide {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn play(&mut self, _rules: &Rules, history: &[Game]) -> Shape {
+        self.plays[history.len() % self.plays.len()]
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+/// Counters the shape the opponent played the most so far, opens with
+/// the first shape of the rules.
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".to_owned()
+    }
+
+    fn play(&mut self, rules: &Rules, history: &[Game]) -> Shape {
+        let expected = most_frequent(rules, history.iter().map(|game| game.opponent));
+        expected.map_or(Shape(0), |shape| counter(rules, shape))
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+/// Picks shapes at random.
+#[derive(Debug, Clone)]
+pub struct RandomPlayer {
+    seed: u64,
+    rng: Rng,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> Self {
+        RandomPlayer {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Strategy for RandomPlayer {
+    fn name(&self) -> String {
+        format!("random({})", self.seed)
+    }
+
+    fn play(&mut self, rules: &Rules, _history: &[Game]) -> Shape {
+        let shapes: Vec<Shape> = rules.shapes().collect();
+        *self.rng.choose(&shapes).unwrap()
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+/// Predicts the opponent's next shape from what followed the last
+/// `order` shapes of the opponent earlier in the match, and counters it.
+/// Expects a repeat of the last shape while there's nothing to go on and
+/// opens with the first shape of the rules.
+#[derive(Debug, Clone)]
+pub struct HistoryPredictor {
+    order: usize,
+}
+
+impl HistoryPredictor {
+    pub fn new(order: usize) -> Self {
+        HistoryPredictor { order }
+    }
+}
+
+impl Strategy for HistoryPredictor {
+    fn name(&self) -> String {
+        format!("predict({})", self.order)
+    }
+
+    fn play(&mut self, rules: &Rules, history: &[Game]) -> Shape {
+        let opponent: Vec<Shape> = history.iter().map(|game| game.opponent).collect();
+        let Some(start) = opponent.len().checked_sub(self.order) else {
+            return opponent
+                .last()
+                .map_or(Shape(0), |shape| counter(rules, *shape));
+        };
+        let context = &opponent[start..];
+
+        let followers = (self.order..opponent.len())
+            .filter(|&idx| &opponent[idx - self.order..idx] == context)
+            .map(|idx| opponent[idx]);
+        let expected = most_frequent(rules, followers).or(opponent.last().copied());
+        expected.map_or(Shape(0), |shape| counter(rules, shape))
+    }
+
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+/// Plays a match, returns the games from the side of either player.
+pub fn play_match(
+    rules: &Rules,
+    one: &mut dyn Strategy,
+    other: &mut dyn Strategy,
+    rounds: usize,
+) -> (Vec<Game>, Vec<Game>) {
+    let mut games_one = Vec::with_capacity(rounds);
+    let mut games_other = Vec::with_capacity(rounds);
+
+    for _ in 0..rounds {
+        let play_one = one.play(rules, &games_one);
+        let play_other = other.play(rules, &games_other);
+        games_one.push(Game {
+            opponent: play_other,
+            player: play_one,
+        });
+        games_other.push(Game {
+            opponent: play_one,
+            player: play_other,
+        });
+    }
+
+    (games_one, games_other)
+}
+
+/// Results of a strategy over all of its matches.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Standing {
+    pub name: String,
+    pub score: u32,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Standing {
+    fn record(&mut self, rules: &Rules, games: &[Game]) {
+        self.score += strategy_guide(rules, games);
+        for game in games {
+            match rules.outcome(game.player, game.opponent) {
+                Outcome::Win => self.wins += 1,
+                Outcome::Draw => self.draws += 1,
+                Outcome::Loss => self.losses += 1,
+            }
+        }
+    }
+}
+
+/// Standings of a tournament, best score first.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Standings(pub Vec<Standing>);
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = Table::new(&["Strategy", "Score", "Wins", "Draws", "Losses"]);
+        for standing in &self.0 {
+            table.row(vec![
+                standing.name.clone(),
+                standing.score.to_string(),
+                standing.wins.to_string(),
+                standing.draws.to_string(),
+                standing.losses.to_string(),
+            ]);
+        }
+        write!(f, "{}", table)
+    }
+}
+
+/// Round robin of strategies under a set of rules.
+pub struct Tournament {
+    rules: Rules,
+    rounds: usize,
+    entrants: Vec<Box<dyn Strategy>>,
+}
+
+impl Tournament {
+    /// Tournament under the classic rules whose matches last `rounds`
+    /// rounds.
+    pub fn new(rounds: usize) -> Self {
+        Tournament::with_rules(Rules::default(), rounds)
+    }
+
+    pub fn with_rules(rules: Rules, rounds: usize) -> Self {
+        Tournament {
+            rules,
+            rounds,
+            entrants: Vec::new(),
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn add(&mut self, strategy: impl Strategy + 'static) {
+        self.entrants.push(Box::new(strategy));
+    }
+
+    /// Plays every strategy against every other one once.
+    pub fn play(&self) -> Standings {
+        let mut standings: Vec<Standing> = self
+            .entrants
+            .iter()
+            .map(|entrant| Standing {
+                name: entrant.name(),
+                ..Standing::default()
+            })
+            .collect();
+
+        for one in 0..self.entrants.len() {
+            for other in one + 1..self.entrants.len() {
+                let (games_one, games_other) = play_match(
+                    &self.rules,
+                    self.entrants[one].clone_box().as_mut(),
+                    self.entrants[other].clone_box().as_mut(),
+                    self.rounds,
+                );
+                standings[one].record(&self.rules, &games_one);
+                standings[other].record(&self.rules, &games_other);
+            }
+        }
+
+        // stable, so entrants with the same score keep their order
+        standings.sort_by_key(|standing| Reverse(standing.score));
+        Standings(standings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_two::Play;
+    use indoc::indoc;
+
+    fn rocks(rules: &Rules) -> Guide {
+        Guide::new(rules, "rocks", vec![Play::Rock.shape()]).unwrap()
+    }
+
+    /// Every shape of the rules in turn.
+    fn cycle(rules: &Rules) -> Guide {
+        Guide::new(rules, "cycle", rules.shapes().collect()).unwrap()
+    }
+
+    /// Outcomes of the first player per round.
+    fn outcomes(
+        rules: &Rules,
+        one: &mut dyn Strategy,
+        other: &mut dyn Strategy,
+        rounds: usize,
+    ) -> String {
+        let (games, _) = play_match(rules, one, other, rounds);
+        games
+            .iter()
+            .map(|game| match rules.outcome(game.player, game.opponent) {
+                Outcome::Win => 'W',
+                Outcome::Draw => 'D',
+                Outcome::Loss => 'L',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn guide() {
+        let rules = Rules::default();
+        let (games, other) = play_match(&rules, &mut cycle(&rules), &mut rocks(&rules), 4);
+        let plays: Vec<Shape> = games.iter().map(|game| game.player).collect();
+        assert_eq!(
+            plays,
+            [Play::Rock, Play::Paper, Play::Scissors, Play::Rock].map(Play::shape)
+        );
+        assert!(other.iter().all(|game| game.player == Play::Rock.shape()));
+        assert_eq!(games[1].opponent, Play::Rock.shape());
+    }
+
+    #[test]
+    fn guide_invalid() {
+        let rules = Rules::default();
+        assert_eq!(
+            Guide::new(&rules, "nothing", Vec::new()).unwrap_err(),
+            Error::parse(1, 1, "Guide without plays")
+        );
+
+        let spock = Rules::rock_paper_scissors_lizard_spock()
+            .shape("spock")
+            .unwrap();
+        assert_eq!(
+            Guide::new(&rules, "spock", vec![Play::Rock.shape(), spock]).unwrap_err(),
+            Error::parse(2, 1, "Unknown shape 4")
+        );
+    }
+
+    #[test]
+    fn frequency_counter() {
+        let rules = Rules::default();
+        assert_eq!(
+            outcomes(&rules, &mut FrequencyCounter, &mut rocks(&rules), 5),
+            "DWWWW"
+        );
+        let mostly_paper = vec![Play::Paper.shape(), Play::Paper.shape(), Play::Rock.shape()];
+        assert_eq!(
+            outcomes(
+                &rules,
+                &mut FrequencyCounter,
+                &mut Guide::new(&rules, "mostly paper", mostly_paper).unwrap(),
+                6
+            ),
+            "LWLWWL"
+        );
+    }
+
+    #[test]
+    fn random_player() {
+        let rules = Rules::default();
+        let plays = |seed| {
+            let (games, _) =
+                play_match(&rules, &mut RandomPlayer::new(seed), &mut rocks(&rules), 20);
+            games.iter().map(|game| game.player).collect::<Vec<_>>()
+        };
+        assert_eq!(plays(1), plays(1));
+        assert_ne!(plays(1), plays(2));
+        for shape in rules.shapes() {
+            assert!(plays(1).contains(&shape));
+        }
+    }
+
+    #[test]
+    fn history_predictor() {
+        // learns the cycle after seeing it once
+        let rules = Rules::default();
+        let outcomes = outcomes(
+            &rules,
+            &mut HistoryPredictor::new(1),
+            &mut cycle(&rules),
+            12,
+        );
+        assert!(outcomes[4..].chars().all(|outcome| outcome == 'W'));
+
+        // the cycle stays unpredictable for counting frequencies
+        let (games, _) = play_match(&rules, &mut FrequencyCounter, &mut cycle(&rules), 30);
+        let wins = games
+            .iter()
+            .filter(|game| rules.beats(game.player, game.opponent))
+            .count();
+        assert!(wins < 20);
+    }
+
+    #[test]
+    fn tournament() {
+        let mut tournament = Tournament::new(30);
+        let rules = tournament.rules().clone();
+        tournament.add(rocks(&rules));
+        tournament.add(cycle(&rules));
+        tournament.add(FrequencyCounter);
+        tournament.add(RandomPlayer::new(2022));
+        tournament.add(HistoryPredictor::new(2));
+
+        let standings = tournament.play();
+        assert_eq!(standings, tournament.play());
+
+        let names: Vec<&str> = standings.0.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names[0], "predict(2)");
+        assert_eq!(names[names.len() - 1], "rocks");
+        for standing in &standings.0 {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 4 * 30);
+        }
+    }
+
+    #[test]
+    fn rpsls_tournament() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let spock = rules.shape("spock").unwrap();
+        let mut tournament = Tournament::with_rules(rules.clone(), 10);
+        tournament.add(Guide::new(&rules, "spocks", vec![spock]).unwrap());
+        tournament.add(cycle(&rules));
+        tournament.add(FrequencyCounter);
+
+        let standings = tournament.play();
+        let frequency = standings.0.iter().find(|s| s.name == "frequency").unwrap();
+        assert_eq!(frequency.wins, 12);
+        for standing in &standings.0 {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 2 * 10);
+        }
+
+        // counters spock after opening with rock, which spock beats
+        let mut spocks = Guide::new(&rules, "spocks", vec![spock]).unwrap();
+        assert_eq!(
+            outcomes(&rules, &mut FrequencyCounter, &mut spocks, 5),
+            "LWWWW"
+        );
+    }
+
+    #[test]
+    fn standings_table() {
+        let mut tournament = Tournament::new(3);
+        let rules = tournament.rules().clone();
+        tournament.add(rocks(&rules));
+        tournament.add(Guide::new(&rules, "papers", vec![Play::Paper.shape()]).unwrap());
+        assert_eq!(
+            tournament.play().to_string(),
+            indoc! {"
+                Strategy | Score | Wins | Draws | Losses
+                ---------+-------+------+-------+-------
+                papers   | 24    | 3    | 0     | 0
+                rocks    | 3     | 0    | 0     | 3
+            "}
+        );
+    }
+}
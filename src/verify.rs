@@ -0,0 +1,186 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+
+/// Known answers of a day, read from an answers file like
+///
+/// ```text
+/// part one: 13140
+/// part two:
+/// ##..##..
+/// ###...##
+/// ```
+///
+/// Values may follow the label or start on the next line, which is
+/// needed for multi-line answers. Parts without a label are unchecked.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Expected {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl FromStr for Expected {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        const LABELS: [&str; 2] = ["part one:", "part two:"];
+
+        let mut parts: [Option<String>; 2] = Default::default();
+        let mut current = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let label = LABELS
+                .iter()
+                .enumerate()
+                .find_map(|(part, label)| Some((part, line.strip_prefix(label)?)));
+
+            match (label, current) {
+                (Some((part, _)), _) if parts[part].is_some() => {
+                    return Err(Error::parse(idx + 1, 1, "Duplicate part"));
+                }
+                (Some((part, value)), _) => {
+                    parts[part] = Some(value.trim().to_owned());
+                    current = Some(part);
+                }
+                (None, Some(part)) => {
+                    let value = parts[part].get_or_insert_with(String::new);
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(line);
+                }
+                (None, None) if line.trim().is_empty() => {}
+                (None, None) => {
+                    return Err(Error::parse(idx + 1, 1, "Expected part label"));
+                }
+            }
+        }
+
+        let [part_one, part_two] =
+            parts.map(|value| value.map(|value| value.trim_end().to_owned()));
+
+        Ok(Expected { part_one, part_two })
+    }
+}
+
+/// Result of comparing a part's answer to the expected one.
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        actual: String,
+        expected: String,
+    },
+    Error(Error),
+    /// No expected answer known
+    Unchecked,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+/// Compares answers by their textual form, so any expected number
+/// matches regardless of the part's integer type.
+pub fn verify(answer: &Result<Answer>, expected: Option<&str>) -> Verdict {
+    match (answer, expected) {
+        (Err(err), _) => Verdict::Error(err.clone()),
+        (Ok(_), None) => Verdict::Unchecked,
+        (Ok(answer), Some(expected)) => {
+            let actual = answer.to_string();
+            if actual.trim_end() == expected.trim_end() {
+                Verdict::Pass
+            } else {
+                Verdict::Fail {
+                    actual,
+                    expected: expected.to_owned(),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = |text: &str| if text.contains('\n') { "\n" } else { " " };
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { actual, expected } => write!(
+                f,
+                "FAIL got{}{}\nexpected{}{}",
+                separator(actual),
+                actual,
+                separator(expected),
+                expected
+            ),
+            Verdict::Error(err) => write!(f, "error: {}", err),
+            Verdict::Unchecked => write!(f, "unchecked"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn parse_expected() {
+        let expected: Expected = indoc! {"
+            part one: 13140
+            part two:
+            ##..##..
+            ###...##
+
+        "}
+        .parse()
+        .unwrap();
+        assert_eq!(
+            expected,
+            Expected {
+                part_one: Some("13140".to_owned()),
+                part_two: Some("##..##..\n###...##".to_owned()),
+            }
+        );
+
+        let expected: Expected = "part two: CMZ\n".parse().unwrap();
+        assert_eq!(expected.part_one, None);
+        assert_eq!(expected.part_two, Some("CMZ".to_owned()));
+    }
+
+    #[test]
+    fn parse_expected_invalid() {
+        assert_eq!(
+            "\n42\n".parse::<Expected>(),
+            Err(Error::parse(2, 1, "Expected part label"))
+        );
+        assert_eq!(
+            "part one: 1\npart one: 2".parse::<Expected>(),
+            Err(Error::parse(2, 1, "Duplicate part"))
+        );
+    }
+
+    #[test]
+    fn verify_answers() {
+        assert_eq!(verify(&Ok(Answer::Number(24)), Some("24")), Verdict::Pass);
+        assert_eq!(
+            verify(&Ok(Answer::Image("#.\n.#\n".to_owned())), Some("#.\n.#")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            verify(&Ok(Answer::from("MCD")), Some("CMZ")),
+            Verdict::Fail {
+                actual: "MCD".to_owned(),
+                expected: "CMZ".to_owned()
+            }
+        );
+        assert_eq!(verify(&Ok(Answer::Number(1)), None), Verdict::Unchecked);
+
+        let err = Error::no_solution("No marker");
+        assert_eq!(verify(&Err(err.clone()), Some("7")), Verdict::Error(err));
+    }
+}
@@ -10,7 +10,7 @@ fn part_one_examples() {
         33549
         35390
     "};
-    assert_eq!(part_one(puzzle_input), 21);
+    assert_eq!(part_one(puzzle_input), Ok(21));
 }
 
 #[test]
@@ -22,5 +22,5 @@ fn part_two_examples() {
         33549
         35390
     "};
-    assert_eq!(part_two(puzzle_input), 8);
+    assert_eq!(part_two(puzzle_input), Ok(8));
 }
@@ -32,7 +32,7 @@ fn part_one_examples() {
             If true: throw to monkey 0
             If false: throw to monkey 1
     "};
-    assert_eq!(part_one(puzzle_input), 10605);
+    assert_eq!(part_one(puzzle_input), Ok(10605));
 }
 
 #[test]
@@ -66,5 +66,5 @@ fn part_two_examples() {
             If true: throw to monkey 0
             If false: throw to monkey 1
     "};
-    assert_eq!(part_two(puzzle_input), 2713310158);
+    assert_eq!(part_two(puzzle_input), Ok(2713310158));
 }
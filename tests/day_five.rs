@@ -14,7 +14,7 @@ fn part_one_examples() {
         move 2 from 2 to 1
         move 1 from 1 to 2
     "};
-    assert_eq!(part_one(puzzle_input), "CMZ");
+    assert_eq!(part_one(puzzle_input).unwrap(), "CMZ");
 }
 
 #[test]
@@ -30,5 +30,5 @@ fn part_two_examples() {
         move 2 from 2 to 1
         move 1 from 1 to 2
     "};
-    assert_eq!(part_two(puzzle_input), "MCD");
+    assert_eq!(part_two(puzzle_input).unwrap(), "MCD");
 }
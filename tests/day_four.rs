@@ -11,7 +11,7 @@ fn part_one_examples() {
         6-6,4-6
         2-6,4-8
     "};
-    assert_eq!(part_one(puzzle_input), 2);
+    assert_eq!(part_one(puzzle_input), Ok(2));
 }
 
 #[test]
@@ -24,5 +24,5 @@ fn part_two_examples() {
         6-6,4-6
         2-6,4-8
     "};
-    assert_eq!(part_two(puzzle_input), 4);
+    assert_eq!(part_two(puzzle_input), Ok(4));
 }
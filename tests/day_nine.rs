@@ -13,7 +13,7 @@ fn part_one_examples() {
         L 5
         R 2
     "};
-    assert_eq!(part_one(puzzle_input), 13);
+    assert_eq!(part_one(puzzle_input), Ok(13));
 }
 
 #[test]
@@ -28,5 +28,5 @@ fn part_two_examples() {
         L 25
         U 20
     "};
-    assert_eq!(part_two(puzzle_input), 36);
+    assert_eq!(part_two(puzzle_input), Ok(36));
 }
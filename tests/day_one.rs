@@ -19,7 +19,7 @@ fn part_one_examples() {
 
         10000
     "};
-    assert_eq!(part_one(puzzle_input), 24000);
+    assert_eq!(part_one(puzzle_input), Ok(24000));
 }
 
 #[test]
@@ -40,5 +40,12 @@ fn part_two_examples() {
 
         10000
     "};
-    assert_eq!(part_two(puzzle_input), 45000);
+    assert_eq!(part_two(puzzle_input), Ok(45000));
+}
+
+#[test]
+fn last_elf_without_trailing_blank_line() {
+    let puzzle_input = "1000\n2000\n\n4000\n\n50000";
+    assert_eq!(part_one(puzzle_input), Ok(50000));
+    assert_eq!(part_two(puzzle_input), Ok(57000));
 }
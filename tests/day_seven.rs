@@ -28,7 +28,7 @@ fn part_one_examples() {
         5626152 d.ext
         7214296 k
     "};
-    assert_eq!(part_one(puzzle_input), 95437);
+    assert_eq!(part_one(puzzle_input), Ok(95437));
 }
 
 #[test]
@@ -58,5 +58,5 @@ fn part_two_examples() {
         5626152 d.ext
         7214296 k
     "};
-    assert_eq!(part_two(puzzle_input), 24933642);
+    assert_eq!(part_two(puzzle_input), Ok(24933642));
 }
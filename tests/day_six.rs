@@ -3,35 +3,35 @@ use aoc2022::day_six::{part_one, part_two};
 #[test]
 fn part_one_examples() {
     let puzzle_input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-    assert_eq!(part_one(puzzle_input), 7);
+    assert_eq!(part_one(puzzle_input), Ok(7));
 
     let puzzle_input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-    assert_eq!(part_one(puzzle_input), 5);
+    assert_eq!(part_one(puzzle_input), Ok(5));
 
     let puzzle_input = "nppdvjthqldpwncqszvftbrmjlhg";
-    assert_eq!(part_one(puzzle_input), 6);
+    assert_eq!(part_one(puzzle_input), Ok(6));
 
     let puzzle_input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-    assert_eq!(part_one(puzzle_input), 10);
+    assert_eq!(part_one(puzzle_input), Ok(10));
 
     let puzzle_input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-    assert_eq!(part_one(puzzle_input), 11);
+    assert_eq!(part_one(puzzle_input), Ok(11));
 }
 
 #[test]
 fn part_two_examples() {
     let puzzle_input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-    assert_eq!(part_two(puzzle_input), 19);
+    assert_eq!(part_two(puzzle_input), Ok(19));
 
     let puzzle_input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
-    assert_eq!(part_two(puzzle_input), 23);
+    assert_eq!(part_two(puzzle_input), Ok(23));
 
     let puzzle_input = "nppdvjthqldpwncqszvftbrmjlhg";
-    assert_eq!(part_two(puzzle_input), 23);
+    assert_eq!(part_two(puzzle_input), Ok(23));
 
     let puzzle_input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
-    assert_eq!(part_two(puzzle_input), 29);
+    assert_eq!(part_two(puzzle_input), Ok(29));
 
     let puzzle_input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-    assert_eq!(part_two(puzzle_input), 26);
+    assert_eq!(part_two(puzzle_input), Ok(26));
 }
@@ -151,7 +151,7 @@ fn part_one_examples() {
         noop
         noop
     "};
-    assert_eq!(part_one(puzzle_input), 13140);
+    assert_eq!(part_one(puzzle_input), Ok(13140));
 }
 
 #[test]
@@ -314,5 +314,5 @@ fn part_two_examples() {
         #######.......#######.......#######.....
     "};
 
-    assert_eq!(part_two(puzzle_input), output);
+    assert_eq!(part_two(puzzle_input).unwrap(), output);
 }
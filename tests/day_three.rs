@@ -11,7 +11,7 @@ fn part_one_examples() {
         ttgJtRGJQctTZtZT
         CrZsJsPPZsGzwwsLwLmpwMDw
     "};
-    assert_eq!(part_one(puzzle_input), 157);
+    assert_eq!(part_one(puzzle_input), Ok(157));
 }
 
 #[test]
@@ -24,5 +24,5 @@ fn part_two_examples() {
         ttgJtRGJQctTZtZT
         CrZsJsPPZsGzwwsLwLmpwMDw
     "};
-    assert_eq!(part_two(puzzle_input), 70);
+    assert_eq!(part_two(puzzle_input), Ok(70));
 }
@@ -10,7 +10,7 @@ fn part_one_examples() {
         acctuvwj
         abdefghi
     "};
-    assert_eq!(part_one(puzzle_input), 31);
+    assert_eq!(part_one(puzzle_input), Ok(31));
 }
 
 #[test]
@@ -22,5 +22,5 @@ fn part_two_examples() {
         acctuvwj
         abdefghi
     "};
-    assert_eq!(part_two(puzzle_input), 2713310158);
+    assert_eq!(part_two(puzzle_input), Ok(29));
 }
@@ -1,4 +1,6 @@
-use aoc2022::day_two::{part_one, part_two};
+use aoc2022::day_two::{best_mapping, part_one, part_two, Play, ShapeMapping};
+use aoc2022::solution;
+use aoc2022::verify::{verify, Expected, Verdict};
 use indoc::indoc;
 
 #[test]
@@ -8,7 +10,7 @@ fn part_one_examples() {
         B X
         C Z
     "};
-    assert_eq!(part_one(puzzle_input), 15);
+    assert_eq!(part_one(puzzle_input), Ok(15));
 }
 
 #[test]
@@ -18,5 +20,47 @@ fn part_two_examples() {
         B X
         C Z
     "};
-    assert_eq!(part_two(puzzle_input), 12);
+    assert_eq!(part_two(puzzle_input), Ok(12));
+}
+
+#[test]
+fn best_mapping_examples() {
+    let puzzle_input = indoc! {"
+        A Y
+        B X
+        C Z
+    "};
+    assert_eq!(
+        best_mapping(puzzle_input),
+        Ok((
+            ShapeMapping::from([Play::Scissors, Play::Paper, Play::Rock]),
+            24
+        ))
+    );
+}
+
+#[test]
+fn example_answers_file() {
+    let puzzle_input = indoc! {"
+        A Y
+        B X
+        C Z
+    "};
+    let expected: Expected = include_str!("fixtures/day2.answers").parse().unwrap();
+    let (part_one, part_two) = solution::day(2).unwrap().parts(puzzle_input);
+    assert_eq!(
+        verify(&part_one, expected.part_one.as_deref()),
+        Verdict::Pass
+    );
+    assert_eq!(
+        verify(&part_two, expected.part_two.as_deref()),
+        Verdict::Pass
+    );
+
+    // without an answers file both parts stay unchecked
+    let expected = Expected::default();
+    assert_eq!(
+        verify(&part_one, expected.part_one.as_deref()),
+        Verdict::Unchecked
+    );
 }